@@ -0,0 +1,51 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use rayon::ThreadPoolBuilder;
+
+use triton_vm::example_programs::FIBONACCI_SEQUENCE;
+use triton_vm::prelude::*;
+use triton_vm::table::master_table::MasterBaseTable;
+
+const FIBONACCI_INDEX: u32 = 1_000;
+
+criterion_main!(benches);
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = fill_base_tables
+}
+
+/// Compare filling the base tables on a single thread to filling them on all available threads.
+///
+/// cargo criterion --bench fill_base_tables
+fn fill_base_tables(c: &mut Criterion) {
+    let program = FIBONACCI_SEQUENCE.clone();
+    let public_input = PublicInput::new(bfe_vec![FIBONACCI_INDEX]);
+    let (aet, _) = program
+        .trace_execution(public_input, NonDeterminism::default())
+        .unwrap();
+
+    let stark = Stark::default();
+    let fri = stark.derive_fri(aet.padded_height()).unwrap();
+    let num_trace_randomizers = stark.num_trace_randomizers;
+
+    let num_available_threads = rayon::current_num_threads();
+    let mut group = c.benchmark_group(format!("fill_base_tables_fib_{FIBONACCI_INDEX}"));
+    for num_threads in [1, num_available_threads] {
+        let thread_pool = ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+        let bench_id = BenchmarkId::new("threads", num_threads);
+        group.bench_function(bench_id, |b| {
+            b.iter(|| {
+                thread_pool.install(|| {
+                    MasterBaseTable::new(&aet, num_trace_randomizers, fri.domain, fri.domain)
+                })
+            })
+        });
+    }
+    group.finish();
+}
@@ -1,6 +1,10 @@
 use std::collections::hash_map::Entry::Occupied;
 use std::collections::hash_map::Entry::Vacant;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
 use std::ops::AddAssign;
 
 use arbitrary::Arbitrary;
@@ -22,6 +26,8 @@ use crate::table::op_stack_table::OpStackTableEntry;
 use crate::table::ram_table::RamTableCall;
 use crate::table::table_column::HashBaseTableColumn::CI;
 use crate::table::table_column::MasterBaseTableColumn;
+use crate::table::table_column::ProcessorBaseTableColumn;
+use crate::table::table_column::RamBaseTableColumn;
 use crate::table::u32_table::U32TableEntry;
 use crate::table::*;
 use crate::vm::CoProcessorCall;
@@ -76,6 +82,44 @@ pub struct AlgebraicExecutionTrace {
 
     /// Records how often each entry in the lookup table was looked up.
     pub lookup_table_lookup_multiplicities: [u64; AlgebraicExecutionTrace::LOOKUP_TABLE_HEIGHT],
+
+    /// All calls to co-processors, in the order they were made. Only recorded if
+    /// [enabled](Self::with_co_processor_call_recording).
+    co_processor_calls: Option<Vec<CoProcessorCall>>,
+}
+
+/// How often each [`Instruction`] was executed during the generation of an
+/// [`AlgebraicExecutionTrace`]. Instructions with different arguments, like `push 1` and
+/// `push 2`, are counted separately.
+///
+/// See [`AlgebraicExecutionTrace::instruction_histogram`].
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Arbitrary)]
+pub struct InstructionHistogram {
+    /// Every executed instruction alongside its execution count, ordered by descending count.
+    pub entries: Vec<(Instruction, u64)>,
+}
+
+/// Receives the parts of an execution trace as they are produced while [tracing the
+/// execution][trace] of a [`Program`]. Allows processing executions whose trace is too big to be
+/// held in memory, for example by streaming the rows to disk or by computing running statistics.
+///
+/// The [`AlgebraicExecutionTrace`] is the in-memory implementation of this trait.
+///
+/// [trace]: Program::trace_execution_into
+pub trait TraceSink {
+    /// The reason recording failed, for example, an [`io::Error`](std::io::Error) of a sink
+    /// writing to disk. Aborts tracing with
+    /// [`TracingError::SinkFailure`](crate::error::TracingError::SinkFailure).
+    type Error;
+
+    /// Record the [`VMState`] right before it executes its current instruction.
+    fn record_state(&mut self, state: &VMState) -> Result<(), Self::Error>;
+
+    /// Record a call to a co-processor, made by the most recently recorded [`VMState`].
+    fn record_co_processor_call(
+        &mut self,
+        co_processor_call: CoProcessorCall,
+    ) -> Result<(), Self::Error>;
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Arbitrary)]
@@ -102,6 +146,7 @@ impl AlgebraicExecutionTrace {
             u32_entries: HashMap::new(),
             cascade_table_lookup_multiplicities: HashMap::new(),
             lookup_table_lookup_multiplicities: [0; Self::LOOKUP_TABLE_HEIGHT],
+            co_processor_calls: None,
         };
         aet.fill_program_hash_trace();
         aet
@@ -145,6 +190,85 @@ impl AlgebraicExecutionTrace {
         }
     }
 
+    /// How often each instruction was executed. Derived from the
+    /// [instruction multiplicities](Self::instruction_multiplicities).
+    pub fn instruction_histogram(&self) -> InstructionHistogram {
+        let mut counts: Vec<(Instruction, u64)> = vec![];
+        let mut instruction_pointer = 0;
+        while let Some(&instruction) = self.program.instructions.get(instruction_pointer) {
+            let multiplicity = u64::from(self.instruction_multiplicities[instruction_pointer]);
+            instruction_pointer += instruction.size();
+            if multiplicity == 0 {
+                continue;
+            }
+            match counts.iter_mut().find(|(i, _)| *i == instruction) {
+                Some((_, count)) => *count += multiplicity,
+                None => counts.push((instruction, multiplicity)),
+            }
+        }
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+        InstructionHistogram { entries: counts }
+    }
+
+    /// For every accessed RAM address, how often it was read from and written to, in this order.
+    /// Derived from the [RAM trace](Self::ram_trace).
+    pub fn ram_access_histogram(&self) -> HashMap<BFieldElement, (u64, u64)> {
+        let pointer_index = RamBaseTableColumn::RamPointer.base_table_index();
+        let instruction_type_index = RamBaseTableColumn::InstructionType.base_table_index();
+
+        let mut histogram = HashMap::<_, (u64, u64)>::new();
+        for row in self.ram_trace.rows() {
+            let (reads, writes) = histogram.entry(row[pointer_index]).or_default();
+            if row[instruction_type_index] == ram_table::INSTRUCTION_TYPE_WRITE {
+                *writes += 1;
+            } else {
+                *reads += 1;
+            }
+        }
+
+        histogram
+    }
+
+    /// The (at most) `k` most frequently accessed RAM addresses alongside their total number of
+    /// accesses, ordered by descending number of accesses. Ties are broken by ascending address.
+    /// See also [`ram_access_histogram`](Self::ram_access_histogram).
+    pub fn most_accessed_ram_addresses(&self, k: usize) -> Vec<(BFieldElement, u64)> {
+        self.ram_access_histogram()
+            .into_iter()
+            .map(|(address, (reads, writes))| (address, reads + writes))
+            .sorted_by_key(|&(address, accesses)| (std::cmp::Reverse(accesses), address.value()))
+            .take(k)
+            .collect()
+    }
+
+    /// The [processor trace](Self::processor_trace) in the “folded stacks” format consumed by
+    /// flame graph tools like [inferno](https://github.com/jonhoo/inferno). Every cycle is one
+    /// sample. The stack of a sample is the chain of subroutines active in that cycle, inferred
+    /// from the jump stack, with the program's entry point at the root. Each subroutine is named
+    /// by its [label](Program::label_for_address).
+    pub fn folded_stacks(&self) -> String {
+        let jsp_index = ProcessorBaseTableColumn::JSP.base_table_index();
+        let jsd_index = ProcessorBaseTableColumn::JSD.base_table_index();
+
+        let mut call_stack = vec![self.program.label_for_address(0)];
+        let mut num_samples = BTreeMap::<_, u64>::new();
+        for row in self.processor_trace.rows() {
+            let call_depth = row[jsp_index].value() as usize + 1;
+            call_stack.truncate(call_depth);
+            if call_stack.len() < call_depth {
+                let callee = row[jsd_index].value();
+                call_stack.push(self.program.label_for_address(callee));
+            }
+            *num_samples.entry(call_stack.join(";")).or_default() += 1;
+        }
+
+        num_samples
+            .into_iter()
+            .map(|(stack, count)| format!("{stack} {count}\n"))
+            .collect()
+    }
+
     /// # Panics
     ///
     /// - if the table height exceeds [`u32::MAX`]
@@ -190,7 +314,7 @@ impl AlgebraicExecutionTrace {
             .try_into()
             .unwrap();
         let program_digest = Digest::new(program_digest);
-        let expected_digest = self.program.hash::<Tip5>();
+        let expected_digest = self.program.hash_with_vm_hasher();
         assert_eq!(expected_digest, program_digest);
     }
 
@@ -208,12 +332,6 @@ impl AlgebraicExecutionTrace {
             .collect()
     }
 
-    pub(crate) fn record_state(&mut self, state: &VMState) -> Result<(), InstructionError> {
-        self.record_instruction_lookup(state.instruction_pointer)?;
-        self.append_state_to_processor_trace(state);
-        Ok(())
-    }
-
     fn record_instruction_lookup(
         &mut self,
         instruction_pointer: usize,
@@ -231,15 +349,23 @@ impl AlgebraicExecutionTrace {
             .unwrap()
     }
 
-    pub(crate) fn record_co_processor_call(&mut self, co_processor_call: CoProcessorCall) {
-        match co_processor_call {
-            Tip5Trace(Instruction::Hash, trace) => self.append_hash_trace(*trace),
-            SpongeStateReset => self.append_initial_sponge_state(),
-            Tip5Trace(instruction, trace) => self.append_sponge_trace(instruction, *trace),
-            U32Call(u32_entry) => self.record_u32_table_entry(u32_entry),
-            OpStackCall(op_stack_entry) => self.record_op_stack_entry(op_stack_entry),
-            RamCall(ram_call) => self.record_ram_call(ram_call),
-        }
+    /// Additionally record every call from the processor to any of the co-processors. Recording
+    /// is disabled by default since it roughly doubles the memory required by the trace. See
+    /// [`Program::trace_execution_with_co_processor_calls`] to trace an execution with recording
+    /// enabled, or pass an AET with recording enabled to [`Program::trace_execution_into`].
+    #[must_use]
+    pub fn with_co_processor_call_recording(mut self) -> Self {
+        self.co_processor_calls = Some(vec![]);
+        self
+    }
+
+    /// All calls from the processor to any of the co-processors, in the order they were made.
+    /// Useful for analyzing which co-processor dominates the cost of proving.
+    ///
+    /// Returns `None` unless [recording was enabled](Self::with_co_processor_call_recording), for
+    /// example, by [`Program::trace_execution_with_co_processor_calls`].
+    pub fn co_processor_calls(&self) -> Option<&[CoProcessorCall]> {
+        self.co_processor_calls.as_deref()
     }
 
     fn append_hash_trace(&mut self, trace: PermutationTrace) {
@@ -337,6 +463,79 @@ impl AlgebraicExecutionTrace {
     }
 }
 
+impl TraceSink for AlgebraicExecutionTrace {
+    type Error = InstructionError;
+
+    fn record_state(&mut self, state: &VMState) -> Result<(), Self::Error> {
+        self.record_instruction_lookup(state.instruction_pointer)?;
+        self.append_state_to_processor_trace(state);
+        Ok(())
+    }
+
+    fn record_co_processor_call(
+        &mut self,
+        co_processor_call: CoProcessorCall,
+    ) -> Result<(), Self::Error> {
+        if let Some(co_processor_calls) = &mut self.co_processor_calls {
+            co_processor_calls.push(co_processor_call.clone());
+        }
+        match co_processor_call {
+            Tip5Trace(Instruction::Hash, trace) => self.append_hash_trace(*trace),
+            SpongeStateReset => self.append_initial_sponge_state(),
+            Tip5Trace(instruction, trace) => self.append_sponge_trace(instruction, *trace),
+            U32Call(u32_entry) => self.record_u32_table_entry(u32_entry),
+            OpStackCall(op_stack_entry) => self.record_op_stack_entry(op_stack_entry),
+            RamCall(ram_call) => self.record_ram_call(ram_call),
+        }
+        Ok(())
+    }
+}
+
+impl InstructionHistogram {
+    /// The total number of executed instructions.
+    pub fn total(&self) -> u64 {
+        self.entries.iter().map(|&(_, count)| count).sum()
+    }
+}
+
+impl Display for InstructionHistogram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        const COL_WIDTH: usize = 12;
+
+        let instructions = self
+            .entries
+            .iter()
+            .map(|(i, _)| i.to_string())
+            .collect_vec();
+        let max_instruction_len = instructions.iter().map(String::len).max();
+        let max_instruction_len = max_instruction_len.unwrap_or_default().max(COL_WIDTH);
+
+        let [instruction_title, count_title, share_title] = ["Instruction", "Count", "Share"];
+        write!(f, "| {instruction_title:<max_instruction_len$} ")?;
+        write!(f, "| {count_title:>COL_WIDTH$} ")?;
+        write!(f, "| {share_title:>COL_WIDTH$} ")?;
+        writeln!(f, "|")?;
+
+        let dash = "-";
+        write!(f, "|:{dash:-<max_instruction_len$}-")?;
+        write!(f, "|-{dash:->COL_WIDTH$}:")?;
+        write!(f, "|-{dash:->COL_WIDTH$}:")?;
+        writeln!(f, "|")?;
+
+        let total = self.total() as f64;
+        for (instruction, &(_, count)) in instructions.iter().zip_eq(&self.entries) {
+            let share = 100.0 * count as f64 / total;
+            let share = format!("{share:.1}%");
+            write!(f, "| {instruction:<max_instruction_len$} ")?;
+            write!(f, "| {count:>COL_WIDTH$} ")?;
+            write!(f, "| {share:>COL_WIDTH$} ")?;
+            writeln!(f, "|")?;
+        }
+
+        Ok(())
+    }
+}
+
 impl TableHeight {
     fn new(table: TableId, height: usize) -> Self {
         Self { table, height }
@@ -358,6 +557,7 @@ impl Ord for TableHeight {
 #[cfg(test)]
 mod tests {
     use assert2::assert;
+    use assert2::let_assert;
 
     use crate::prelude::*;
 
@@ -385,4 +585,105 @@ mod tests {
             let _ = aet.height_of_table(table);
         }
     }
+
+    #[test]
+    fn hash_instruction_results_in_one_hash_co_processor_call() {
+        let program = triton_program!(hash pop 5 halt);
+        let (aet, _) = program
+            .trace_execution_with_co_processor_calls([].into(), [].into())
+            .unwrap();
+
+        let_assert!(Some(co_processor_calls) = aet.co_processor_calls());
+        let is_hash_call = |call: &&_| matches!(call, Tip5Trace(Instruction::Hash, _));
+        let hash_calls = co_processor_calls.iter().filter(is_hash_call);
+        assert!(1 == hash_calls.count());
+    }
+
+    #[test]
+    fn co_processor_calls_are_not_recorded_by_default() {
+        let program = triton_program!(hash pop 5 halt);
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        assert!(aet.co_processor_calls().is_none());
+    }
+
+    #[test]
+    fn instruction_histogram_counts_executed_instructions() {
+        let program = triton_program! {
+            push 3 call loop
+            push 1 pop 1 halt
+            loop:
+                dup 0 push 0 eq skiz return
+                push -1 add recurse
+        };
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        let histogram = aet.instruction_histogram();
+
+        let num_cycles = aet.processor_trace.nrows() as u64;
+        assert!(num_cycles == histogram.total());
+
+        let count_of = |instruction| {
+            let entry = histogram.entries.iter().find(|&&(i, _)| i == instruction);
+            entry.map(|&(_, count)| count).unwrap_or_default()
+        };
+        assert!(1 == count_of(Instruction::Push(bfe!(3))));
+        assert!(1 == count_of(Instruction::Halt));
+        assert!(4 == count_of(Instruction::Push(bfe!(0))));
+        assert!(3 == count_of(Instruction::Recurse));
+        assert!(1 == count_of(Instruction::Return));
+        assert!(0 == count_of(Instruction::Nop));
+
+        let counts = histogram.entries.iter().map(|&(_, count)| count);
+        assert!(counts.tuple_windows().all(|(a, b)| a >= b));
+
+        let table = histogram.to_string();
+        let num_header_lines = 2;
+        assert!(num_header_lines + histogram.entries.len() == table.lines().count());
+        assert!(table.starts_with("| Instruction"));
+        assert!(table.lines().any(|line| line.starts_with("| recurse ")));
+    }
+
+    #[test]
+    fn ram_access_histogram_counts_reads_and_writes_per_address() {
+        let program = triton_program! {
+            push 42 push 5 write_mem 1 pop 1
+            push 43 push 5 write_mem 1 pop 1
+            push 5 read_mem 1 pop 2
+            push 7 read_mem 1 pop 2
+            push 7 read_mem 1 pop 2
+            push 7 read_mem 1 pop 2
+            push 7 read_mem 1 pop 2
+            halt
+        };
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        let histogram = aet.ram_access_histogram();
+
+        assert!(2 == histogram.len());
+        assert!(Some(&(1, 2)) == histogram.get(&bfe!(5)));
+        assert!(Some(&(4, 0)) == histogram.get(&bfe!(7)));
+
+        assert!(vec![(bfe!(7), 4)] == aet.most_accessed_ram_addresses(1));
+        let all_addresses = vec![(bfe!(7), 4), (bfe!(5), 3)];
+        assert!(all_addresses == aet.most_accessed_ram_addresses(10));
+    }
+
+    #[test]
+    fn folded_stacks_attribute_every_cycle_to_active_call_stack() {
+        let program = triton_program! {
+            main:
+                call foo
+                call foo
+                halt
+            foo:
+                push 1 pop 1
+                call bar
+                return
+            bar:
+                push 2 pop 1
+                return
+        };
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+
+        let expected = "main 3\nmain;foo 8\nmain;foo;bar 6\n";
+        assert!(expected == aet.folded_stacks());
+    }
 }
@@ -0,0 +1,460 @@
+//! Compact storage of [`Program`]s, independent of their canonical [encoding](BFieldCodec).
+//! See [`Codebook`].
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use twenty_first::prelude::*;
+
+use crate::error::CodebookError;
+use crate::error::ProgramDecompressionError;
+use crate::instruction::Instruction;
+use crate::instruction::ALL_INSTRUCTIONS;
+use crate::program::Program;
+
+type Result<T> = std::result::Result<T, ProgramDecompressionError>;
+
+/// A prefix-free code for the opcodes of Triton VM's [instructions](Instruction), built from
+/// the opcode frequencies in some representative [`Program`]s. Frequent instructions get short
+/// codes, making [compressed](Program::to_compressed) programs small.
+///
+/// Compression is intended for storage only. It is unrelated to the canonical
+/// [encoding](BFieldCodec::encode) of a program, which remains the basis for
+/// [hashing](Program::hash). A compressed program can only be
+/// [decompressed](Program::from_compressed) with the codebook used for compressing it.
+///
+/// Every instruction has a code, even if it does not occur in any of the programs the codebook
+/// is built from.
+///
+/// Since the code is canonical, the codebook is fully determined by its
+/// [code lengths](Self::code_lengths). Those are what gets (de)serialized, allowing to persist
+/// the codebook alongside the programs compressed with it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Codebook {
+    codes: HashMap<u32, Code>,
+    opcodes: HashMap<Code, u32>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+struct Code {
+    bits: u64,
+    len: u32,
+}
+
+impl Codebook {
+    /// Build a [Huffman code](https://en.wikipedia.org/wiki/Huffman_coding) from the
+    /// frequencies of the opcodes in the given programs.
+    pub fn from_programs(programs: &[Program]) -> Self {
+        let mut frequencies = ALL_INSTRUCTIONS
+            .map(|instruction| (instruction.opcode(), 1_u64))
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        for instruction in programs.iter().flat_map(|p| p.clone().into_iter()) {
+            *frequencies.entry(instruction.opcode()).or_default() += 1;
+        }
+
+        Self::from_code_lengths(Self::huffman_code_lengths(&frequencies))
+    }
+
+    /// Restore a codebook from its [code lengths](Self::code_lengths).
+    ///
+    /// Fails if some instruction has no code, if some opcode does not belong to any instruction,
+    /// or if the code lengths don't describe a complete prefix-free code.
+    pub fn try_from_code_lengths(
+        code_lengths: BTreeMap<u32, u32>,
+    ) -> std::result::Result<Self, CodebookError> {
+        const MAX_CODE_LEN: u32 = u64::BITS;
+
+        for instruction in ALL_INSTRUCTIONS {
+            if !code_lengths.contains_key(&instruction.opcode()) {
+                return Err(CodebookError::MissingInstruction(instruction));
+            }
+        }
+        for &opcode in code_lengths.keys() {
+            if Instruction::try_from(opcode).is_err() {
+                return Err(CodebookError::UnknownOpcode(opcode));
+            }
+        }
+
+        // Kraft's equality holds if and only if the code lengths describe a complete code.
+        let mut kraft_sum = 0_u128;
+        for &len in code_lengths.values() {
+            if !(1..=MAX_CODE_LEN).contains(&len) {
+                return Err(CodebookError::InvalidCodeLengths);
+            }
+            kraft_sum += 1 << (MAX_CODE_LEN - len);
+        }
+        if kraft_sum != 1 << MAX_CODE_LEN {
+            return Err(CodebookError::InvalidCodeLengths);
+        }
+
+        Ok(Self::from_code_lengths(code_lengths))
+    }
+
+    /// The length, in bits, of the code for every opcode. Determines the codebook uniquely, see
+    /// [`try_from_code_lengths`](Self::try_from_code_lengths).
+    pub fn code_lengths(&self) -> BTreeMap<u32, u32> {
+        self.codes
+            .iter()
+            .map(|(&opcode, code)| (opcode, code.len))
+            .collect()
+    }
+
+    fn huffman_code_lengths(frequencies: &HashMap<u32, u64>) -> HashMap<u32, u32> {
+        let mut code_lengths = frequencies
+            .keys()
+            .map(|&opcode| (opcode, 0))
+            .collect::<HashMap<_, _>>();
+        let mut heap = frequencies
+            .iter()
+            .map(|(&opcode, &frequency)| Reverse((frequency, vec![opcode])))
+            .collect::<BinaryHeap<_>>();
+
+        // the last remaining node is the tree's root, which is not needed
+        while let (Some(Reverse(lhs)), Some(Reverse(rhs))) = (heap.pop(), heap.pop()) {
+            let (lhs_frequency, mut opcodes) = lhs;
+            let (rhs_frequency, rhs_opcodes) = rhs;
+            opcodes.extend(rhs_opcodes);
+            for opcode in &opcodes {
+                if let Some(len) = code_lengths.get_mut(opcode) {
+                    *len += 1;
+                }
+            }
+            heap.push(Reverse((lhs_frequency + rhs_frequency, opcodes)));
+        }
+
+        code_lengths
+    }
+
+    /// Assign the canonical Huffman code given the code lengths.
+    fn from_code_lengths(code_lengths: impl IntoIterator<Item = (u32, u32)>) -> Self {
+        let mut codes = HashMap::new();
+        let mut bits = 0;
+        let mut previous_len = 0;
+        for (opcode, len) in code_lengths
+            .into_iter()
+            .sorted_by_key(|&(opcode, len)| (len, opcode))
+        {
+            bits <<= len - previous_len;
+            codes.insert(opcode, Code { bits, len });
+            bits += 1;
+            previous_len = len;
+        }
+        let opcodes = codes
+            .iter()
+            .map(|(&opcode, &code)| (code, opcode))
+            .collect();
+
+        Self { codes, opcodes }
+    }
+
+    /// The length, in bits, of the code for the given instruction's opcode.
+    pub fn code_len(&self, instruction: Instruction) -> usize {
+        self.codes[&instruction.opcode()].len as usize
+    }
+
+    pub(crate) fn compress(&self, program: &Program) -> Vec<u8> {
+        let instructions = program.clone().into_iter().collect_vec();
+        let mut writer = BitWriter::default();
+        writer.write_varint(instructions.len() as u64);
+        for instruction in instructions {
+            let code = self.codes[&instruction.opcode()];
+            writer.write_bits(code.bits, code.len);
+            if let Some(arg) = instruction.arg() {
+                writer.write_varint(zigzag(arg));
+            }
+        }
+
+        writer.bytes
+    }
+
+    pub(crate) fn decompress(&self, bytes: &[u8]) -> Result<Vec<Instruction>> {
+        let mut reader = BitReader { bytes, position: 0 };
+        let num_instructions = reader.read_varint()?;
+
+        // instantiating with claimed capacity is a potential DOS vector
+        let mut instructions = vec![];
+        while (instructions.len() as u64) < num_instructions {
+            let index = instructions.len();
+            let invalid_instruction =
+                |err| ProgramDecompressionError::InvalidInstruction(index, err);
+            let opcode = self.read_opcode(&mut reader)?;
+            let mut instruction = Instruction::try_from(opcode).map_err(invalid_instruction)?;
+            if instruction.arg().is_some() {
+                let arg = unzigzag(reader.read_varint()?)?;
+                instruction = instruction.change_arg(arg).map_err(invalid_instruction)?;
+            }
+            instructions.push(instruction);
+        }
+
+        if reader.position.div_ceil(8) != bytes.len() {
+            return Err(ProgramDecompressionError::TrailingData);
+        }
+        Ok(instructions)
+    }
+
+    fn read_opcode(&self, reader: &mut BitReader) -> Result<u32> {
+        // Since Huffman codes are complete, some prefix of any long enough bit string is a code.
+        let mut code = Code { bits: 0, len: 0 };
+        loop {
+            code.bits = (code.bits << 1) | reader.read_bit()?;
+            code.len += 1;
+            if let Some(&opcode) = self.opcodes.get(&code) {
+                return Ok(opcode);
+            }
+        }
+    }
+}
+
+impl Serialize for Codebook {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.code_lengths().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Codebook {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let code_lengths = BTreeMap::deserialize(deserializer)?;
+        Self::try_from_code_lengths(code_lengths).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Map field elements close to 0 to small integers, no matter their "sign".
+fn zigzag(element: BFieldElement) -> u64 {
+    let value = element.value();
+    if value <= BFieldElement::P / 2 {
+        2 * value
+    } else {
+        2 * (BFieldElement::P - value) - 1
+    }
+}
+
+fn unzigzag(zigzagged: u64) -> Result<BFieldElement> {
+    let half = zigzagged.div_ceil(2);
+    if half > BFieldElement::P / 2 {
+        return Err(ProgramDecompressionError::ValueOutOfRange);
+    }
+
+    let value = if zigzagged % 2 == 0 {
+        half
+    } else {
+        BFieldElement::P - half
+    };
+    Ok(bfe!(value))
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    num_bits: usize,
+}
+
+impl BitWriter {
+    fn write_bit(&mut self, bit: bool) {
+        if self.num_bits % 8 == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            self.bytes[self.num_bits / 8] |= 1 << (7 - self.num_bits % 8);
+        }
+        self.num_bits += 1;
+    }
+
+    fn write_bits(&mut self, bits: u64, len: u32) {
+        for i in (0..len).rev() {
+            self.write_bit((bits >> i) & 1 == 1);
+        }
+    }
+
+    /// Groups of 7 bits, least significant group first, each preceded by a continuation bit.
+    fn write_varint(&mut self, mut value: u64) {
+        loop {
+            let group = value & 0x7f;
+            value >>= 7;
+            self.write_bit(value != 0);
+            self.write_bits(group, 7);
+            if value == 0 {
+                return;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn read_bit(&mut self) -> Result<u64> {
+        let Some(&byte) = self.bytes.get(self.position / 8) else {
+            return Err(ProgramDecompressionError::UnexpectedEndOfData);
+        };
+        let bit = (byte >> (7 - self.position % 8)) & 1;
+        self.position += 1;
+
+        Ok(u64::from(bit))
+    }
+
+    fn read_bits(&mut self, len: u32) -> Result<u64> {
+        let mut bits = 0;
+        for _ in 0..len {
+            bits = (bits << 1) | self.read_bit()?;
+        }
+        Ok(bits)
+    }
+
+    fn read_varint(&mut self) -> Result<u64> {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let is_continued = self.read_bit()? == 1;
+            let group = self.read_bits(7)?;
+            let Some(shifted_group) = group.checked_shl(shift).filter(|g| g >> shift == group)
+            else {
+                return Err(ProgramDecompressionError::ValueOutOfRange);
+            };
+            value |= shifted_group;
+            shift += 7;
+            if !is_continued {
+                return Ok(value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use crate::example_programs::FIBONACCI_SEQUENCE;
+    use crate::example_programs::MERKLE_TREE_AUTHENTICATION_PATH_VERIFY;
+    use crate::op_stack::NumberOfWords;
+    use crate::triton_program;
+
+    use super::*;
+
+    #[proptest]
+    fn compressing_then_decompressing_is_identity(
+        #[strategy(arb())] program: Program,
+        #[strategy(arb())] other_programs: Vec<Program>,
+    ) {
+        let codebook = Codebook::from_programs(&other_programs);
+        let compressed = program.to_compressed(&codebook);
+        let decompressed = Program::from_compressed(&compressed, &codebook)?;
+        prop_assert_eq!(program, decompressed);
+    }
+
+    #[proptest]
+    fn zigzag_then_unzigzag_is_identity(#[strategy(arb())] element: BFieldElement) {
+        prop_assert_eq!(element, unzigzag(zigzag(element))?);
+    }
+
+    #[test]
+    fn field_elements_close_to_zero_are_zigzagged_to_small_integers() {
+        assert!(0 == zigzag(bfe!(0)));
+        assert!(1 == zigzag(bfe!(-1)));
+        assert!(2 == zigzag(bfe!(1)));
+        assert!(3 == zigzag(bfe!(-2)));
+    }
+
+    #[test]
+    fn frequent_instructions_get_short_codes() {
+        let program = triton_program!(pop 1 pop 1 pop 1 pop 1 pop 1 pop 1 halt);
+        let codebook = Codebook::from_programs(&vec![program; 50]);
+        let pop = Instruction::Pop(NumberOfWords::N1);
+        assert!(codebook.code_len(pop) < codebook.code_len(Instruction::Halt));
+        assert!(codebook.code_len(Instruction::Halt) < codebook.code_len(Instruction::Nop));
+    }
+
+    #[test]
+    fn compressed_programs_are_smaller_than_encoded_programs() {
+        let programs = [
+            FIBONACCI_SEQUENCE.clone(),
+            MERKLE_TREE_AUTHENTICATION_PATH_VERIFY.clone(),
+        ];
+        let codebook = Codebook::from_programs(&programs);
+        for program in programs {
+            let num_encoded_bytes = program.encode().len() * 8;
+            let num_compressed_bytes = program.to_compressed(&codebook).len();
+            assert!(num_compressed_bytes < num_encoded_bytes);
+        }
+    }
+
+    #[test]
+    fn decompressing_truncated_program_fails() {
+        let program = FIBONACCI_SEQUENCE.clone();
+        let codebook = Codebook::from_programs(&[program.clone()]);
+        let compressed = program.to_compressed(&codebook);
+
+        let truncated = &compressed[..compressed.len() - 1];
+        let err = Program::from_compressed(truncated, &codebook).unwrap_err();
+        assert!(ProgramDecompressionError::UnexpectedEndOfData == err);
+    }
+
+    #[test]
+    fn decompressing_program_with_trailing_data_fails() {
+        let program = FIBONACCI_SEQUENCE.clone();
+        let codebook = Codebook::from_programs(&[program.clone()]);
+        let mut compressed = program.to_compressed(&codebook);
+        compressed.push(0);
+
+        let_assert!(Err(err) = Program::from_compressed(&compressed, &codebook));
+        assert!(ProgramDecompressionError::TrailingData == err);
+    }
+
+    #[proptest]
+    fn saving_then_loading_codebook_allows_decompressing(
+        #[strategy(arb())] program: Program,
+        #[strategy(arb())] other_programs: Vec<Program>,
+    ) {
+        let codebook = Codebook::from_programs(&other_programs);
+        let compressed = program.to_compressed(&codebook);
+
+        let saved_codebook = serde_json::to_string(&codebook).unwrap();
+        let loaded_codebook: Codebook = serde_json::from_str(&saved_codebook).unwrap();
+        prop_assert_eq!(&codebook, &loaded_codebook);
+
+        let decompressed = Program::from_compressed(&compressed, &loaded_codebook)?;
+        prop_assert_eq!(program, decompressed);
+    }
+
+    #[test]
+    fn codebook_is_restored_from_its_code_lengths() {
+        let codebook = Codebook::from_programs(&[FIBONACCI_SEQUENCE.clone()]);
+        let_assert!(Ok(restored) = Codebook::try_from_code_lengths(codebook.code_lengths()));
+        assert!(codebook == restored);
+    }
+
+    #[test]
+    fn incomplete_code_lengths_are_rejected() {
+        let codebook = Codebook::from_programs(&[]);
+        let mut code_lengths = codebook.code_lengths();
+        *code_lengths.values_mut().max().unwrap() += 1;
+
+        let_assert!(Err(err) = Codebook::try_from_code_lengths(code_lengths));
+        assert!(CodebookError::InvalidCodeLengths == err);
+    }
+
+    #[test]
+    fn code_lengths_missing_an_instruction_are_rejected() {
+        let codebook = Codebook::from_programs(&[]);
+        let mut code_lengths = codebook.code_lengths();
+        code_lengths.remove(&Instruction::Halt.opcode());
+
+        let_assert!(Err(err) = Codebook::try_from_code_lengths(code_lengths));
+        assert!(CodebookError::MissingInstruction(Instruction::Halt) == err);
+    }
+}
@@ -3,17 +3,25 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use std::num::TryFromIntError;
 
+use itertools::Itertools;
 use thiserror::Error;
 use twenty_first::error::MerkleTreeError;
 use twenty_first::prelude::*;
 
 use crate::instruction::Instruction;
+use crate::parser::ParseError;
+use crate::program::DebugInfo;
 use crate::proof_item::ProofItem;
 use crate::proof_item::ProofItemVariant;
 use crate::proof_stream::ProofStream;
+use crate::table::master_table::TableId;
+use crate::table::ConstraintType;
 use crate::vm::VMState;
 use crate::BFieldElement;
 
+/// The number of op-stack words captured in [`InstructionError::AssertionFailed`].
+pub const NUM_ASSERTION_CONTEXT_WORDS: usize = 5;
+
 /// Indicates a runtime error that resulted in a crash of Triton VM.
 #[derive(Debug, Clone, Eq, PartialEq, Error)]
 pub struct VMError {
@@ -29,6 +37,21 @@ impl VMError {
         let vm_state = Box::new(vm_state);
         Self { source, vm_state }
     }
+
+    /// Like the [`Display`] implementation, but additionally names the source code location of
+    /// the instruction that caused the crash, if that location is known to the [`DebugInfo`].
+    /// Falls back to the [`Display`] implementation otherwise.
+    pub fn to_string_with_debug_info(&self, debug_info: Option<&DebugInfo>) -> String {
+        let instruction_pointer = self.vm_state.instruction_pointer;
+        let Some(location) = debug_info.and_then(|info| info.source_location(instruction_pointer))
+        else {
+            return self.to_string();
+        };
+
+        let source = self.source;
+        let vm_state = &self.vm_state;
+        format!("VM error: {source} {location}\nVM state:\n{vm_state}\n")
+    }
 }
 
 impl Display for VMError {
@@ -39,6 +62,33 @@ impl Display for VMError {
     }
 }
 
+impl From<TracingError<InstructionError>> for VMError {
+    fn from(err: TracingError<InstructionError>) -> Self {
+        match err {
+            TracingError::VMError(err) => err,
+            TracingError::SinkFailure { source, vm_state } => Self { source, vm_state },
+        }
+    }
+}
+
+/// The ways [tracing an execution into a sink](crate::program::Program::trace_execution_into)
+/// can fail. Generic over the [error of the sink](crate::aet::TraceSink::Error).
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum TracingError<E> {
+    #[error(transparent)]
+    VMError(#[from] VMError),
+
+    #[error("the trace sink failed in cycle {}", vm_state.cycle_count)]
+    SinkFailure {
+        /// The reason the trace sink failed.
+        source: E,
+
+        /// The state of Triton VM the trace sink failed on.
+        vm_state: Box<VMState>,
+    },
+}
+
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
 pub enum InstructionError {
@@ -60,11 +110,41 @@ pub enum InstructionError {
     #[error("jump stack is empty")]
     JumpStackIsEmpty,
 
-    #[error("assertion failed: st0 must be 1")]
-    AssertionFailed,
+    #[error(
+        "assertion failed in cycle {cycle} at instruction pointer {instruction_pointer}: \
+        st0 must be 1; top of stack: [{}]",
+        .context.iter().join(", ")
+    )]
+    AssertionFailed {
+        /// The cycle in which the failing `assert` was executed.
+        cycle: u32,
+
+        /// The address of the failing `assert`.
+        instruction_pointer: usize,
 
-    #[error("vector assertion failed: stack[{0}] != stack[{}]", .0 + tip5::DIGEST_LENGTH)]
-    VectorAssertionFailed(usize),
+        /// The top-most words of the op-stack at the moment of failure, starting with `st0`.
+        context: [BFieldElement; NUM_ASSERTION_CONTEXT_WORDS],
+    },
+
+    #[error(
+        "vector assertion failed: stack[{index}] != stack[{}], i.e., {} != {}; \
+        vectors: [{}] and [{}]",
+        .index.saturating_add(tip5::DIGEST_LENGTH),
+        element_at(.lhs, *.index),
+        element_at(.rhs, *.index),
+        .lhs.iter().join(", "),
+        .rhs.iter().join(", "),
+    )]
+    VectorAssertionFailed {
+        /// The index of the first element in which the two vectors differ.
+        index: usize,
+
+        /// The vector on top of the op stack, starting with `st0`.
+        lhs: [BFieldElement; tip5::DIGEST_LENGTH],
+
+        /// The vector below the top-most vector, starting with `st5`.
+        rhs: [BFieldElement; tip5::DIGEST_LENGTH],
+    },
 
     #[error("0 does not have a multiplicative inverse")]
     InverseOfZero,
@@ -81,17 +161,94 @@ pub enum InstructionError {
     #[error("failed to convert BFieldElement {0} into u32")]
     FailedU32Conversion(BFieldElement),
 
-    #[error("public input buffer is empty after {0} reads")]
-    EmptyPublicInput(usize),
-
-    #[error("secret input buffer is empty after {0} reads")]
-    EmptySecretInput(usize),
+    #[error("{kind} input exhausted: {requested} elements requested but {available} available")]
+    InputExhausted {
+        kind: InputKind,
+        requested: usize,
+        available: usize,
+    },
 
     #[error("no more secret digests available")]
     EmptySecretDigestInput,
 
     #[error("Triton VM has halted and cannot execute any further instructions")]
     MachineHalted,
+
+    #[error("execution did not halt within the cycle limit of {0}")]
+    CycleLimitExceeded(u32),
+}
+
+/// The element at the given index, or a placeholder if the index is out of bounds.
+fn element_at(vector: &[BFieldElement], index: usize) -> String {
+    vector
+        .get(index)
+        .map_or_else(|| "?".to_string(), |element| element.to_string())
+}
+
+/// The kind of input a program [ran out of](InstructionError::InputExhausted).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum InputKind {
+    /// Read using instruction `read_io`.
+    Public,
+
+    /// Read using instruction `divine`.
+    Secret,
+}
+
+impl Display for InputKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InputKind::Public => write!(f, "public"),
+            InputKind::Secret => write!(f, "secret"),
+        }
+    }
+}
+
+/// The ways [verifying a state transition](VMState::verify_transition) can fail.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum TransitionError {
+    #[error(transparent)]
+    InstructionError(#[from] InstructionError),
+
+    #[error("the {0} of the successor state does not result from executing one instruction")]
+    Mismatch(StateComponent),
+}
+
+/// A part of a [`VMState`], as named in a [`TransitionError::Mismatch`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum StateComponent {
+    InstructionPointer,
+    CycleCount,
+    OpStack,
+    JumpStack,
+    Ram,
+    Sponge,
+    PublicInput,
+    PublicOutput,
+    SecretInput,
+    Program,
+    Halting,
+}
+
+impl Display for StateComponent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            StateComponent::InstructionPointer => "instruction pointer",
+            StateComponent::CycleCount => "cycle count",
+            StateComponent::OpStack => "op stack",
+            StateComponent::JumpStack => "jump stack",
+            StateComponent::Ram => "RAM",
+            StateComponent::Sponge => "sponge state",
+            StateComponent::PublicInput => "public input",
+            StateComponent::PublicOutput => "public output",
+            StateComponent::SecretInput => "secret input",
+            StateComponent::Program => "program",
+            StateComponent::Halting => "halting flag",
+        };
+        write!(f, "{name}")
+    }
 }
 
 #[non_exhaustive]
@@ -122,10 +279,44 @@ pub enum ProofStreamError {
     #[error("the proof stream must contain exactly one log2_padded_height item")]
     TooManyLog2PaddedHeights,
 
+    #[error("the proof stream must contain a log2_fri_domain_length item")]
+    NoLog2FriDomainLength,
+
+    #[error("the proof stream must contain exactly one log2_fri_domain_length item")]
+    TooManyLog2FriDomainLengths,
+
+    #[error("the FRI domain length 2^{0} does not fit into a usize")]
+    FriDomainLengthTooLarge(u32),
+
+    #[error("the proof stream must contain a fri_expansion_factor item")]
+    NoFriExpansionFactor,
+
+    #[error("the proof stream must contain exactly one fri_expansion_factor item")]
+    TooManyFriExpansionFactors,
+
     #[error(transparent)]
     DecodingError(#[from] <ProofStream as BFieldCodec>::Error),
 }
 
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum ProofDecodingError {
+    #[error("the byte sequence is too short to contain a header")]
+    MissingHeader,
+
+    #[error("the byte sequence does not start with the expected magic bytes")]
+    UnexpectedMagicBytes,
+
+    #[error("proof byte format version {0} is not supported")]
+    UnsupportedVersion(u32),
+
+    #[error("the length of the body ({0} bytes) is not a multiple of 8")]
+    IncompleteElement(usize),
+
+    #[error("the encoding of field element {0} is not canonical")]
+    NonCanonicalElement(u64),
+}
+
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
 pub enum FriSetupError {
@@ -205,6 +396,152 @@ pub enum ProgramDecodingError {
     MissingArgument(usize, Instruction),
 }
 
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum ProgramDecompressionError {
+    #[error("compressed program ends unexpectedly")]
+    UnexpectedEndOfData,
+
+    #[error("compressed program contains an out-of-range value")]
+    ValueOutOfRange,
+
+    #[error("compressed program contains invalid instruction at index {0}: {1}")]
+    InvalidInstruction(usize, InstructionError),
+
+    #[error("compressed program is followed by trailing data")]
+    TrailingData,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum CodebookError {
+    #[error("instruction {0} has no code")]
+    MissingInstruction(Instruction),
+
+    #[error("opcode {0} does not correspond to any instruction")]
+    UnknownOpcode(u32),
+
+    #[error("code lengths do not describe a complete prefix-free code")]
+    InvalidCodeLengths,
+}
+
+/// A violated invariant between two consecutive [`VMState`]s.
+/// See [`VMState::invariants_ok`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum InvariantViolation {
+    #[error("clock must increment by 1, but went from {previous} to {current}")]
+    ClockIncrement { previous: u32, current: u32 },
+
+    #[error("the program must not change during execution")]
+    ProgramChanged,
+
+    #[error(
+        "instruction `{instruction}` must change the op stack's size by {expected}, \
+        but changed it by {actual}"
+    )]
+    OpStackSizeChange {
+        instruction: Instruction,
+        expected: i32,
+        actual: i32,
+    },
+
+    #[error("jump stack's size must change by at most 1, but went from {previous} to {current}")]
+    JumpStackSizeChange { previous: usize, current: usize },
+
+    #[error("public input must be consumed from the front")]
+    PublicInputNotConsumedInOrder,
+
+    #[error("public output must only ever be appended to")]
+    PublicOutputNotAppendOnly,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum ProgramValidationError {
+    #[error("no path through the program's main body ends in instruction `halt`")]
+    MissingHalt,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum StackError {
+    #[error("the instruction at address {address} underflows the op stack")]
+    Underflow { address: usize },
+
+    #[error("cannot rule out that the op stack underflows, see address {address}")]
+    Unknown { address: usize },
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum ProgramBuildingError {
+    #[error("label \"{0}\" is defined more than once")]
+    DuplicateLabel(String),
+
+    #[error("label \"{0}\" is used but never defined")]
+    UndefinedLabel(String),
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum SubroutineExtractionError {
+    #[error("label \"{0}\" does not exist")]
+    UnknownLabel(String),
+
+    #[error("subroutine \"{0}\" never returns")]
+    MissingReturn(String),
+
+    #[error("the `call` at address {address} targets address {target} outside the subroutine")]
+    CallOutsideSubroutine { address: usize, target: u64 },
+}
+
+/// The ways [re-tracing](crate::program::Program::retrace_from) an execution can fail.
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum RetraceError {
+    #[error("changed cycle {changed_cycle} is not among the {num_prior_states} prior states")]
+    ChangedCycleOutOfBounds {
+        changed_cycle: usize,
+        num_prior_states: usize,
+    },
+
+    #[error(transparent)]
+    VMError(#[from] VMError),
+}
+
+/// An AIR constraint that does not evaluate to zero on the execution trace.
+/// See [`Stark::constraint_violations`](crate::stark::Stark::constraint_violations).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Error)]
+#[error(
+    "{constraint_type} constraint {table_constraint_index} of {} is violated in row {row_index}",
+    constraint_origin(.table)
+)]
+pub struct ConstraintViolation {
+    pub constraint_type: ConstraintType,
+
+    /// The index of the violated constraint among the master table's constraints of the same
+    /// type.
+    pub constraint_index: usize,
+
+    /// The table the violated constraint belongs to, or `None` for constraints of the
+    /// [Grand Cross-Table Argument](crate::table::cross_table_argument::GrandCrossTableArg).
+    pub table: Option<TableId>,
+
+    /// The index of the violated constraint among the constraints of the same type that
+    /// belong to the [`table`](Self::table).
+    pub table_constraint_index: usize,
+
+    pub row_index: usize,
+}
+
+fn constraint_origin(table: &Option<TableId>) -> String {
+    match table {
+        Some(table) => format!("table {table}"),
+        None => "the Grand Cross-Table Argument".to_string(),
+    }
+}
+
 #[non_exhaustive]
 #[derive(Debug, Clone, Eq, PartialEq, Error)]
 pub enum ProvingError {
@@ -214,6 +551,9 @@ pub enum ProvingError {
     #[error("claimed public output does not match actual public output")]
     PublicOutputMismatch,
 
+    #[error(transparent)]
+    ConstraintViolation(#[from] ConstraintViolation),
+
     #[error("expected row of length {expected_len} but got {actual_len}")]
     TableRowConversionError {
         expected_len: usize,
@@ -236,6 +576,45 @@ pub enum ProvingError {
     VMError(#[from] VMError),
 }
 
+/// The ways [proving a program given as source code](crate::prove_source) can fail. Running the
+/// program is part of proving it; crashes of Triton VM are reported as
+/// [`ProvingError::VMError`].
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Error)]
+pub enum SourceProvingError<'a> {
+    // The parse error borrows the source code and can therefore not be the error's `source`.
+    #[error("{0}")]
+    ParseError(ParseError<'a>),
+
+    #[error(transparent)]
+    ProvingError(#[from] ProvingError),
+}
+
+impl<'a> From<ParseError<'a>> for SourceProvingError<'a> {
+    fn from(err: ParseError<'a>) -> Self {
+        Self::ParseError(err)
+    }
+}
+
+/// The ways [verifying a proof for a program given as source code](crate::verify_source) can
+/// fail.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum SourceVerificationError<'a> {
+    // The parse error borrows the source code and can therefore not be the error's `source`.
+    #[error("{0}")]
+    ParseError(ParseError<'a>),
+
+    #[error(transparent)]
+    VerificationError(#[from] VerificationError),
+}
+
+impl<'a> From<ParseError<'a>> for SourceVerificationError<'a> {
+    fn from(err: ParseError<'a>) -> Self {
+        Self::ParseError(err)
+    }
+}
+
 #[non_exhaustive]
 #[derive(Debug, Error)]
 pub enum VerificationError {
@@ -269,6 +648,12 @@ pub enum VerificationError {
     #[error("the number of received extension table rows does not match the parameters")]
     IncorrectNumberOfExtTableRows,
 
+    #[error("the proof's FRI domain length {proof} does not match the parameters' {expected}")]
+    FriDomainLengthMismatch { proof: usize, expected: usize },
+
+    #[error("the proof's FRI expansion factor {proof} does not match the parameters' {expected}")]
+    FriExpansionFactorMismatch { proof: usize, expected: usize },
+
     #[error(transparent)]
     ProofStreamError(#[from] ProofStreamError),
 
@@ -280,6 +665,12 @@ pub enum VerificationError {
 
     #[error(transparent)]
     FriValidationError(#[from] FriValidationError),
+
+    #[error("FRI round {round} failed: {source}")]
+    FriRoundFailure {
+        round: usize,
+        source: FriValidationError,
+    },
 }
 
 #[non_exhaustive]
@@ -310,10 +701,21 @@ mod tests {
     use proptest_arbitrary_interop::arb;
     use test_strategy::proptest;
 
+    use crate::program::Program;
     use crate::triton_program;
 
     use super::*;
 
+    #[test]
+    fn displaying_vector_assertion_failure_with_out_of_bounds_index_does_not_panic() {
+        let err = InstructionError::VectorAssertionFailed {
+            index: usize::MAX,
+            lhs: bfe_array![0, 1, 2, 3, 4],
+            rhs: bfe_array![5, 6, 7, 8, 9],
+        };
+        assert!(err.to_string().contains("? != ?"));
+    }
+
     #[test]
     fn instruction_pointer_overflow() {
         let program = triton_program!(nop);
@@ -346,7 +748,43 @@ mod tests {
     fn assert_false() {
         let program = triton_program!(push 0 assert halt);
         let_assert!(Err(err) = program.run([].into(), [].into()));
-        let_assert!(InstructionError::AssertionFailed = err.source);
+        let_assert!(InstructionError::AssertionFailed { .. } = err.source);
+    }
+
+    #[test]
+    fn failing_assert_reports_cycle_instruction_pointer_and_top_of_stack() {
+        let program = triton_program!(push 42 push 1 assert push 0 assert halt);
+        let_assert!(Err(err) = program.run([].into(), [].into()));
+        let_assert!(
+            InstructionError::AssertionFailed {
+                cycle,
+                instruction_pointer,
+                context,
+            } = err.source
+        );
+        assert!(4 == cycle);
+        assert!(7 == instruction_pointer);
+        assert!(bfe!(0) == context[0]);
+        assert!(bfe!(42) == context[1]);
+    }
+
+    #[test]
+    fn error_rendered_with_debug_info_names_source_location() {
+        let code = "push 1 call foo halt\nfoo:\n  push 0\n  assert\n  return";
+        let (program, debug_info) = Program::from_code_with_debug_info(code).unwrap();
+        let_assert!(Err(err) = program.run([].into(), [].into()));
+
+        let annotated = err.to_string_with_debug_info(Some(&debug_info));
+        assert!(annotated.contains("at foo (line 4)"));
+        assert!(err.to_string() == err.to_string_with_debug_info(None));
+    }
+
+    #[test]
+    fn error_rendered_with_debug_info_falls_back_to_plain_display_for_unknown_address() {
+        let (program, debug_info) = Program::from_code_with_debug_info("nop").unwrap();
+        let_assert!(Err(err) = program.run([].into(), [].into()));
+        let_assert!(InstructionError::InstructionPointerOverflow = err.source);
+        assert!(err.to_string() == err.to_string_with_debug_info(Some(&debug_info)));
     }
 
     #[test]
@@ -357,10 +795,27 @@ mod tests {
             assert_vector halt
         };
         let_assert!(Err(err) = program.run([].into(), [].into()));
-        let_assert!(InstructionError::VectorAssertionFailed(index) = err.source);
+        let_assert!(InstructionError::VectorAssertionFailed { index, .. } = err.source);
         assert!(1 == index);
     }
 
+    #[test]
+    fn failing_vector_assertion_reports_first_differing_elements() {
+        let program = triton_program! {
+            push 4 push 3 push 2 push 10 push 0
+            push 4 push 3 push 20 push 1 push 0
+            assert_vector halt
+        };
+        let_assert!(Err(err) = program.run([].into(), [].into()));
+        let_assert!(InstructionError::VectorAssertionFailed { index, lhs, rhs } = err.source);
+        assert!(1 == index);
+        assert!(bfe_array![0, 1, 20, 3, 4] == lhs);
+        assert!(bfe_array![0, 10, 2, 3, 4] == rhs);
+
+        let message = err.source.to_string();
+        assert!(message.contains("stack[1] != stack[6], i.e., 1 != 10"));
+    }
+
     #[proptest]
     fn assert_unequal_vec(
         #[strategy(arb())] test_vector: [BFieldElement; tip5::DIGEST_LENGTH],
@@ -390,7 +845,7 @@ mod tests {
         };
 
         let_assert!(Err(err) = program.run([].into(), [].into()));
-        let_assert!(InstructionError::VectorAssertionFailed(index) = err.source);
+        let_assert!(InstructionError::VectorAssertionFailed { index, .. } = err.source);
         prop_assert_eq!(disturbance_index, index);
     }
 
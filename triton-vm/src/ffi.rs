@@ -0,0 +1,322 @@
+//! A C-compatible interface for embedding Triton VM into a host written in another language.
+//! Requires feature `ffi`.
+//!
+//! A [program](TritonProgram) is [parsed](triton_program_parse) into an opaque handle, which can
+//! then be [run](triton_program_run) any number of times. The public output of the most recent
+//! run can be [queried](triton_program_output) from the handle. Once no longer needed, the handle
+//! must be [freed](triton_program_free).
+//!
+//! Field elements are passed and returned as their canonical `u64` representation, together with
+//! an explicit length. Every function reports failure through a [`TritonStatus`]; no panic ever
+//! unwinds across the boundary.
+//!
+//! # Building
+//!
+//! To build a static or dynamic library, run
+//!
+//! ```sh
+//! cargo rustc --package triton-vm --features ffi --release --crate-type staticlib
+//! ```
+//!
+//! or use `--crate-type cdylib`, respectively.
+//!
+//! # Header Generation
+//!
+//! All signatures are compatible with [cbindgen](https://github.com/mozilla/cbindgen). A C header
+//! can be generated by running
+//!
+//! ```sh
+//! cbindgen --crate triton-vm --lang c --output triton_vm.h
+//! ```
+//!
+//! with a `cbindgen.toml` that contains
+//!
+//! ```toml
+//! [parse.expand]
+//! features = ["ffi"]
+//! ```
+
+use std::panic::catch_unwind;
+use std::panic::AssertUnwindSafe;
+
+use crate::prelude::*;
+
+/// The outcome of calling any function of the [C interface](self).
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TritonStatus {
+    /// The call succeeded.
+    Ok = 0,
+
+    /// A required pointer was null.
+    NullPointer = 1,
+
+    /// The program's source code is not valid UTF-8.
+    InvalidUtf8 = 2,
+
+    /// The program's source code is not valid Triton assembly.
+    ParseError = 3,
+
+    /// Some input is not the canonical representation of a field element.
+    NonCanonicalInput = 4,
+
+    /// The program crashed during execution.
+    ExecutionError = 5,
+
+    /// The provided buffer is too small to hold the requested data.
+    BufferTooSmall = 6,
+
+    /// Triton VM panicked. This indicates a bug in Triton VM.
+    Panic = 7,
+}
+
+/// An opaque handle to a parsed [`Program`] and the public output of its most recent run.
+#[derive(Debug, Clone)]
+pub struct TritonProgram {
+    program: Program,
+    public_output: Vec<BFieldElement>,
+}
+
+/// Parse the UTF-8 encoded [Triton assembly](crate::triton_asm) of length `code_len` at `code`.
+/// On success, a handle to the parsed program is written to `program_out`. The handle must be
+/// released using [`triton_program_free`].
+///
+/// # Safety
+///
+/// - `code` must be valid for reads of `code_len` bytes, and
+/// - `program_out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn triton_program_parse(
+    code: *const u8,
+    code_len: usize,
+    program_out: *mut *mut TritonProgram,
+) -> TritonStatus {
+    guarded(|| {
+        if program_out.is_null() {
+            return Err(TritonStatus::NullPointer);
+        }
+        let code = slice_from_raw_parts(code, code_len)?;
+        let code = std::str::from_utf8(code).map_err(|_| TritonStatus::InvalidUtf8)?;
+        let program = Program::from_code(code).map_err(|_| TritonStatus::ParseError)?;
+        let program = TritonProgram {
+            program,
+            public_output: vec![],
+        };
+        program_out.write(Box::into_raw(Box::new(program)));
+        Ok(())
+    })
+}
+
+/// Run the program behind the given handle on the given public and secret input. On success, the
+/// public output can be retrieved using [`triton_program_output`]. On failure, the public output
+/// is empty.
+///
+/// # Safety
+///
+/// - `program` must be a handle obtained from [`triton_program_parse`] that has not been freed,
+/// - `public_input` must be valid for reads of `public_input_len` elements, and
+/// - `secret_input` must be valid for reads of `secret_input_len` elements.
+///
+/// Pointers to inputs of length 0 may be null.
+#[no_mangle]
+pub unsafe extern "C" fn triton_program_run(
+    program: *mut TritonProgram,
+    public_input: *const u64,
+    public_input_len: usize,
+    secret_input: *const u64,
+    secret_input_len: usize,
+) -> TritonStatus {
+    guarded(|| {
+        let program = program.as_mut().ok_or(TritonStatus::NullPointer)?;
+        let public_input = slice_from_raw_parts(public_input, public_input_len)?;
+        let secret_input = slice_from_raw_parts(secret_input, secret_input_len)?;
+        let public_input = PublicInput::new(to_field_elements(public_input)?);
+        let non_determinism = NonDeterminism::new(to_field_elements(secret_input)?);
+
+        program.public_output.clear();
+        program.public_output = program
+            .program
+            .run(public_input, non_determinism)
+            .map_err(|_| TritonStatus::ExecutionError)?;
+        Ok(())
+    })
+}
+
+/// Write the number of elements in the public output of the most recent run of the program
+/// behind the given handle to `len_out`.
+///
+/// # Safety
+///
+/// - `program` must be a handle obtained from [`triton_program_parse`] that has not been freed,
+///   and
+/// - `len_out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn triton_program_output_len(
+    program: *const TritonProgram,
+    len_out: *mut usize,
+) -> TritonStatus {
+    guarded(|| {
+        let program = program.as_ref().ok_or(TritonStatus::NullPointer)?;
+        if len_out.is_null() {
+            return Err(TritonStatus::NullPointer);
+        }
+        len_out.write(program.public_output.len());
+        Ok(())
+    })
+}
+
+/// Copy the public output of the most recent run of the program behind the given handle into the
+/// buffer `output` of capacity `output_len`. Fails with [`TritonStatus::BufferTooSmall`] if the
+/// buffer cannot hold the entire [output](triton_program_output_len).
+///
+/// # Safety
+///
+/// - `program` must be a handle obtained from [`triton_program_parse`] that has not been freed,
+///   and
+/// - `output` must be valid for writes of `output_len` elements.
+///
+/// If `output_len` is 0, `output` may be null.
+#[no_mangle]
+pub unsafe extern "C" fn triton_program_output(
+    program: *const TritonProgram,
+    output: *mut u64,
+    output_len: usize,
+) -> TritonStatus {
+    guarded(|| {
+        let program = program.as_ref().ok_or(TritonStatus::NullPointer)?;
+        let public_output = &program.public_output;
+        if output_len < public_output.len() {
+            return Err(TritonStatus::BufferTooSmall);
+        }
+        if public_output.is_empty() {
+            return Ok(());
+        }
+        if output.is_null() {
+            return Err(TritonStatus::NullPointer);
+        }
+        let output = std::slice::from_raw_parts_mut(output, public_output.len());
+        for (out, element) in output.iter_mut().zip(public_output) {
+            *out = element.value();
+        }
+        Ok(())
+    })
+}
+
+/// Release the given handle. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `program` must be null or a handle obtained from [`triton_program_parse`] that has not been
+/// freed.
+#[no_mangle]
+pub unsafe extern "C" fn triton_program_free(program: *mut TritonProgram) {
+    if program.is_null() {
+        return;
+    }
+    drop(Box::from_raw(program));
+}
+
+fn guarded(f: impl FnOnce() -> Result<(), TritonStatus>) -> TritonStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => TritonStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => TritonStatus::Panic,
+    }
+}
+
+/// # Safety
+///
+/// If `len` is not 0, `data` must be valid for reads of `len` elements.
+unsafe fn slice_from_raw_parts<'a, T>(data: *const T, len: usize) -> Result<&'a [T], TritonStatus> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if data.is_null() {
+        return Err(TritonStatus::NullPointer);
+    }
+    Ok(std::slice::from_raw_parts(data, len))
+}
+
+fn to_field_elements(values: &[u64]) -> Result<Vec<BFieldElement>, TritonStatus> {
+    values
+        .iter()
+        .map(|&value| {
+            if value >= BFieldElement::P {
+                return Err(TritonStatus::NonCanonicalInput);
+            }
+            Ok(BFieldElement::new(value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use assert2::assert;
+
+    use super::*;
+
+    fn parse(code: &str) -> *mut TritonProgram {
+        let mut program = ptr::null_mut();
+        let status = unsafe { triton_program_parse(code.as_ptr(), code.len(), &mut program) };
+        assert!(TritonStatus::Ok == status);
+        program
+    }
+
+    #[test]
+    fn parse_run_and_fetch_output() {
+        let program = parse("read_io 1 divine 1 mul write_io 1 halt");
+        let public_input = [21];
+        let secret_input = [2];
+        let status = unsafe {
+            triton_program_run(program, public_input.as_ptr(), 1, secret_input.as_ptr(), 1)
+        };
+        assert!(TritonStatus::Ok == status);
+
+        let mut output_len = 0;
+        let status = unsafe { triton_program_output_len(program, &mut output_len) };
+        assert!(TritonStatus::Ok == status);
+        assert!(1 == output_len);
+
+        let mut output = vec![0; output_len];
+        let status = unsafe { triton_program_output(program, output.as_mut_ptr(), output_len) };
+        assert!(TritonStatus::Ok == status);
+        assert!(vec![42] == output);
+
+        unsafe { triton_program_free(program) };
+    }
+
+    #[test]
+    fn errors_are_reported_through_status_codes() {
+        let code = "push 1 foo";
+        let mut program = ptr::null_mut();
+        let status = unsafe { triton_program_parse(code.as_ptr(), code.len(), &mut program) };
+        assert!(TritonStatus::ParseError == status);
+
+        let invalid_utf8 = [0xc3, 0x28];
+        let status = unsafe { triton_program_parse(invalid_utf8.as_ptr(), 2, &mut program) };
+        assert!(TritonStatus::InvalidUtf8 == status);
+
+        let status = unsafe { triton_program_run(ptr::null_mut(), ptr::null(), 0, ptr::null(), 0) };
+        assert!(TritonStatus::NullPointer == status);
+
+        let program = parse("read_io 1 write_io 1 halt");
+        let non_canonical = [u64::MAX];
+        let status =
+            unsafe { triton_program_run(program, non_canonical.as_ptr(), 1, ptr::null(), 0) };
+        assert!(TritonStatus::NonCanonicalInput == status);
+
+        let status = unsafe { triton_program_run(program, ptr::null(), 0, ptr::null(), 0) };
+        assert!(TritonStatus::ExecutionError == status);
+
+        let public_input = [7];
+        let status =
+            unsafe { triton_program_run(program, public_input.as_ptr(), 1, ptr::null(), 0) };
+        assert!(TritonStatus::Ok == status);
+        let status = unsafe { triton_program_output(program, ptr::null_mut(), 0) };
+        assert!(TritonStatus::BufferTooSmall == status);
+
+        unsafe { triton_program_free(program) };
+    }
+}
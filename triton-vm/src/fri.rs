@@ -581,6 +581,47 @@ impl<H: AlgebraicHasher> Fri<H> {
         Ok(verifier.first_round_partially_revealed_codeword())
     }
 
+    /// A debugging aid, not part of regular [verification](Self::verify). Verifies the proof
+    /// on the proof stream round by round and returns the index of the first round that fails
+    /// verification, together with the reason. Returns `None` if verification succeeds.
+    ///
+    /// Since the folded codewords of all rounds are only compared to a received codeword in the
+    /// last round, a folding inconsistency in any round is attributed to the last round.
+    pub fn first_failing_round(
+        &self,
+        proof_stream: &mut ProofStream,
+    ) -> Option<(usize, FriValidationError)> {
+        let last_round_number = self.num_rounds();
+        let mut verifier = self.verifier(proof_stream);
+        if let Err(err) = verifier.initialize() {
+            let failing_round = verifier.rounds.len().min(last_round_number);
+            return Some((failing_round, err));
+        }
+
+        verifier.sample_first_round_collinearity_check_indices();
+        let first_round_authentication = verifier
+            .receive_partial_codeword_a_for_first_round()
+            .and_then(|auth| verifier.authenticate_partial_codeword_a_for_first_round(auth));
+        if let Err(err) = first_round_authentication {
+            return Some((0, err));
+        }
+
+        for round_number in 0..last_round_number {
+            let round_authentication = verifier
+                .receive_partial_codeword_b_for_round(round_number)
+                .and_then(|auth| {
+                    verifier.authenticate_partial_codeword_b_for_round(round_number, auth)
+                });
+            if let Err(err) = round_authentication {
+                return Some((round_number, err));
+            }
+        }
+
+        verifier.successively_fold_partial_codeword_of_each_round();
+        let err = verifier.authenticate_last_round_codeword().err()?;
+        Some((last_round_number, err))
+    }
+
     fn verifier<'stream>(&'stream self, proof_stream: &'stream mut ProofStream) -> FriVerifier<H> {
         FriVerifier {
             proof_stream,
@@ -927,6 +968,66 @@ mod tests {
         }
     }
 
+    #[proptest(cases = 50)]
+    fn valid_proof_has_no_failing_round(
+        #[strategy(arbitrary_fri())] fri: Fri<Tip5>,
+        #[strategy(-1_i64..=#fri.first_round_max_degree() as i64)] _degree: i64,
+        #[strategy(arbitrary_polynomial_of_degree(#_degree))] polynomial: Polynomial<XFieldElement>,
+    ) {
+        let codeword = fri.domain.evaluate(&polynomial);
+        let mut proof_stream = ProofStream::new();
+        fri.prove(&codeword, &mut proof_stream).unwrap();
+
+        let mut proof_stream = prepare_proof_stream_for_verification(proof_stream);
+        prop_assert!(fri.first_failing_round(&mut proof_stream).is_none());
+    }
+
+    #[proptest(cases = 50)]
+    fn corrupting_revealed_leaves_of_some_round_is_attributed_to_that_round(
+        #[strategy(arbitrary_fri())] fri: Fri<Tip5>,
+        #[strategy(arbitrary_polynomial())] polynomial: Polynomial<XFieldElement>,
+        #[strategy(0..=#fri.num_rounds())] fri_response_index: usize,
+    ) {
+        let codeword = fri.domain.evaluate(&polynomial);
+        let mut proof_stream = ProofStream::new();
+        fri.prove(&codeword, &mut proof_stream).unwrap();
+
+        let mut proof_stream = prepare_proof_stream_for_verification(proof_stream);
+        let mut fri_responses = proof_stream
+            .items
+            .iter_mut()
+            .filter_map(fri_response_filter());
+        let fri_response = fri_responses.nth(fri_response_index).unwrap();
+        for leaf in &mut fri_response.revealed_leaves {
+            *leaf += xfe!(1);
+        }
+
+        // The first response reveals the first round's codeword at the a-indices, every
+        // following response reveals the codeword of the next round at the b-indices.
+        let corrupted_round = fri_response_index.saturating_sub(1);
+        let_assert!(Some((failing_round, err)) = fri.first_failing_round(&mut proof_stream));
+        assert!(corrupted_round == failing_round);
+        assert!(let BadMerkleAuthenticationPath = err);
+    }
+
+    #[proptest(cases = 50)]
+    fn corrupting_last_round_codeword_is_attributed_to_last_round(
+        #[strategy(arbitrary_fri())] fri: Fri<Tip5>,
+        #[strategy(arbitrary_polynomial())] polynomial: Polynomial<XFieldElement>,
+        rng_seed: u64,
+    ) {
+        let codeword = fri.domain.evaluate(&polynomial);
+        let mut proof_stream = ProofStream::new();
+        fri.prove(&codeword, &mut proof_stream).unwrap();
+
+        let proof_stream = prepare_proof_stream_for_verification(proof_stream);
+        let mut proof_stream =
+            modify_last_round_codeword_in_proof_stream_using_seed(proof_stream, rng_seed);
+
+        let_assert!(Some((failing_round, _)) = fri.first_failing_round(&mut proof_stream));
+        assert!(fri.num_rounds() == failing_round);
+    }
+
     #[proptest(cases = 50)]
     fn revealing_wrong_number_of_leaves_results_in_validation_failure(
         #[strategy(arbitrary_fri())] fri: Fri<Tip5>,
@@ -32,6 +32,10 @@ pub type Instruction = AnInstruction<BFieldElement>;
 pub const ALL_INSTRUCTIONS: [Instruction; Instruction::COUNT] = all_instructions_without_args();
 pub const ALL_INSTRUCTION_NAMES: [&str; Instruction::COUNT] = all_instruction_names();
 
+/// The mnemonic, opcode, and whether it takes an argument, for every [`Instruction`]. Useful for
+/// tooling outside of Rust, like external assemblers or syntax highlighters.
+pub const MNEMONIC_TABLE: [(&str, u32, bool); Instruction::COUNT] = mnemonic_table();
+
 lazy_static! {
     pub static ref OPCODE_TO_INSTRUCTION_MAP: HashMap<u32, Instruction> = {
         let mut opcode_to_instruction_map = HashMap::new();
@@ -42,7 +46,12 @@ lazy_static! {
     };
 }
 
-/// A `LabelledInstruction` has `call` addresses encoded as label names.
+/// A `LabelledInstruction` has `call` addresses, as well as addresses pushed using
+/// `push &<label>`, encoded as label names.
+///
+/// More kinds of labelled instructions might be added in the future. Matching exhaustively is
+/// therefore not possible outside of this crate.
+#[non_exhaustive]
 #[derive(Debug, Clone, Eq, PartialEq, Hash, EnumCount)]
 pub enum LabelledInstruction {
     /// An instructions from the [instruction set architecture][isa].
@@ -56,6 +65,10 @@ pub enum LabelledInstruction {
     Breakpoint,
 
     TypeHint(TypeHint),
+
+    /// Instruction `push` with the address of the given label as its argument. Looks like
+    /// "`push &<name>`". Useful for computing jump tables or dynamic call targets.
+    PushLabelAddress(String),
 }
 
 /// A hint about a range of stack elements. Helps debugging programs written for Triton VM.
@@ -120,11 +133,18 @@ impl LabelledInstruction {
     pub const fn op_stack_size_influence(&self) -> i32 {
         match self {
             LabelledInstruction::Instruction(instruction) => instruction.op_stack_size_influence(),
+            LabelledInstruction::PushLabelAddress(_) => 1,
             _ => 0,
         }
     }
 }
 
+impl From<AnInstruction<String>> for LabelledInstruction {
+    fn from(instruction: AnInstruction<String>) -> Self {
+        Self::Instruction(instruction)
+    }
+}
+
 impl Display for LabelledInstruction {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
@@ -132,6 +152,7 @@ impl Display for LabelledInstruction {
             LabelledInstruction::Label(label) => write!(f, "{label}:"),
             LabelledInstruction::Breakpoint => write!(f, "break"),
             LabelledInstruction::TypeHint(type_hint) => write!(f, "{type_hint}"),
+            LabelledInstruction::PushLabelAddress(label) => write!(f, "push &{label}"),
         }
     }
 }
@@ -266,7 +287,8 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
         }
     }
 
-    pub(crate) const fn name(&self) -> &'static str {
+    /// The instruction's name as used in Triton assembly.
+    pub const fn mnemonic(&self) -> &'static str {
         match self {
             Pop(_) => "pop",
             Push(_) => "push",
@@ -317,7 +339,7 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
         BFieldElement::new(self.opcode() as u64)
     }
 
-    pub fn size(&self) -> usize {
+    pub const fn size(&self) -> usize {
         match self {
             Pop(_) | Push(_) => 2,
             Divine(_) => 2,
@@ -446,7 +468,7 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
 
 impl<Dest: Display + PartialEq + Default> Display for AnInstruction<Dest> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "{}", self.name())?;
+        write!(f, "{}", self.mnemonic())?;
         match self {
             Push(arg) => write!(f, " {arg}"),
             Pop(arg) | Divine(arg) => write!(f, " {arg}"),
@@ -460,6 +482,15 @@ impl<Dest: Display + PartialEq + Default> Display for AnInstruction<Dest> {
 }
 
 impl Instruction {
+    /// All instructions of Triton VM in canonical order. Instructions that take an argument
+    /// carry a representative one, like `push 0` or `pop 1`. See also [`ALL_INSTRUCTIONS`].
+    ///
+    /// Note that the [opcodes](Self::opcode) are unique but not contiguous: they encode certain
+    /// properties of the instruction in their lower bits.
+    pub fn all_instructions() -> Vec<Self> {
+        ALL_INSTRUCTIONS.to_vec()
+    }
+
     /// Get the argument of the instruction, if it has one.
     pub fn arg(&self) -> Option<BFieldElement> {
         match self {
@@ -474,7 +505,26 @@ impl Instruction {
 
     /// Change the argument of the instruction, if it has one. Returns an `Err` if the instruction
     /// does not have an argument or if the argument is out of range.
+    ///
+    /// Equivalent to [`with_arg`](Self::with_arg).
     pub fn change_arg(self, new_arg: BFieldElement) -> Result<Self> {
+        self.with_arg(new_arg)
+    }
+
+    /// The same instruction, but with the given argument. Useful for building programs that
+    /// compute, for example, the targets of `call`s or the values to `push`.
+    ///
+    /// Returns an `Err` if the instruction does not take an argument, like `add`, or if the
+    /// argument is out of range, like `swap 16`.
+    ///
+    /// ```
+    /// # use triton_vm::prelude::*;
+    /// # use triton_vm::instruction::Instruction;
+    /// let call = Instruction::Call(bfe!(0)).with_arg(bfe!(42)).unwrap();
+    /// assert_eq!(Instruction::Call(bfe!(42)), call);
+    /// assert!(Instruction::Add.with_arg(bfe!(42)).is_err());
+    /// ```
+    pub fn with_arg(self, new_arg: BFieldElement) -> Result<Self> {
         let illegal_argument_error = InstructionError::IllegalArgument(self, new_arg);
         let num_words = new_arg.try_into().map_err(|_| illegal_argument_error);
         let op_stack_element = new_arg.try_into().map_err(|_| illegal_argument_error);
@@ -535,6 +585,34 @@ impl TryFrom<BFieldElement> for Instruction {
     }
 }
 
+/// Matches a single [`Instruction`]. A sequence of matchers forms a pattern that can be
+/// [searched for](crate::program::Program::find_pattern) in a program.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum InstructionMatcher {
+    /// Matches any instruction.
+    Any,
+
+    /// Matches instruction `push`, irrespective of its argument.
+    AnyPush,
+
+    /// Matches any instruction with the given opcode, irrespective of its argument.
+    Opcode(u32),
+
+    /// Matches exactly the given instruction, including its argument.
+    Exact(Instruction),
+}
+
+impl InstructionMatcher {
+    pub fn matches(self, instruction: Instruction) -> bool {
+        match self {
+            Self::Any => true,
+            Self::AnyPush => matches!(instruction, Push(_)),
+            Self::Opcode(opcode) => instruction.opcode() == opcode,
+            Self::Exact(expected) => instruction == expected,
+        }
+    }
+}
+
 const fn all_instructions_without_args() -> [AnInstruction<BFieldElement>; Instruction::COUNT] {
     [
         Pop(N1),
@@ -586,12 +664,27 @@ const fn all_instruction_names() -> [&'static str; Instruction::COUNT] {
     let mut names = [""; Instruction::COUNT];
     let mut i = 0;
     while i < Instruction::COUNT {
-        names[i] = ALL_INSTRUCTIONS[i].name();
+        names[i] = ALL_INSTRUCTIONS[i].mnemonic();
         i += 1;
     }
     names
 }
 
+const fn mnemonic_table() -> [(&'static str, u32, bool); Instruction::COUNT] {
+    let mut table = [("", 0, false); Instruction::COUNT];
+    let mut i = 0;
+    while i < Instruction::COUNT {
+        let instruction = ALL_INSTRUCTIONS[i];
+        table[i] = (
+            instruction.mnemonic(),
+            instruction.opcode(),
+            instruction.size() > 1,
+        );
+        i += 1;
+    }
+    table
+}
+
 /// Indicators for all the possible bits in an [`Instruction`].
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, EnumCount, EnumIter)]
 pub enum InstructionBit {
@@ -652,6 +745,10 @@ impl<'a> Arbitrary<'a> for LabelledInstruction {
             1 => return Ok(Self::Label(u.arbitrary::<InstructionLabel>()?.into())),
             2 => return Ok(Self::Breakpoint),
             3 => return Ok(Self::TypeHint(u.arbitrary()?)),
+            4 => {
+                let label = u.arbitrary::<InstructionLabel>()?.into();
+                return Ok(Self::PushLabelAddress(label));
+            }
             _ => unreachable!(),
         };
         let legal_label = String::from(u.arbitrary::<InstructionLabel>()?);
@@ -756,6 +853,7 @@ mod tests {
     use strum::IntoEnumIterator;
     use twenty_first::prelude::*;
 
+    use crate::error::InstructionError;
     use crate::instruction::*;
     use crate::op_stack::NUM_OP_STACK_REGISTERS;
     use crate::program::PublicInput;
@@ -811,7 +909,7 @@ mod tests {
     fn computed_and_actual_opcodes_are_identical() {
         for instruction in Instruction::iter() {
             let opcode = instruction.computed_opcode();
-            let name = instruction.name();
+            let name = instruction.mnemonic();
             println!("{opcode: >3} {name}");
         }
 
@@ -834,6 +932,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn opcode_table_is_consistent() {
+        let all_instructions = Instruction::all_instructions();
+        assert!(Instruction::COUNT == all_instructions.len());
+
+        let opcodes = all_instructions.iter().map(|i| i.opcode()).collect_vec();
+        assert!(opcodes.iter().all_unique());
+        for (instruction, opcode) in all_instructions.iter().zip_eq(opcodes) {
+            assert!(bfe!(opcode) == instruction.opcode_b());
+            assert!(*instruction == Instruction::try_from(opcode).unwrap());
+        }
+
+        let mnemonics = all_instructions.iter().map(|i| i.mnemonic()).collect_vec();
+        assert!(mnemonics.iter().all_unique());
+        assert!(ALL_INSTRUCTION_NAMES.to_vec() == mnemonics);
+    }
+
+    #[test]
+    fn mnemonic_table_lists_every_instruction_exactly_once() {
+        let mnemonics = MNEMONIC_TABLE.map(|(mnemonic, _, _)| mnemonic);
+        assert!(ALL_INSTRUCTION_NAMES == mnemonics);
+
+        let opcodes = MNEMONIC_TABLE.map(|(_, opcode, _)| opcode);
+        assert!(opcodes.iter().all_unique());
+
+        for (mnemonic, opcode, has_arg) in MNEMONIC_TABLE {
+            let instruction = Instruction::try_from(opcode).unwrap();
+            assert!(mnemonic == instruction.mnemonic());
+            assert!(has_arg == instruction.arg().is_some());
+        }
+    }
+
     #[test]
     fn number_of_instruction_bits_is_correct() {
         let all_opcodes = Instruction::iter().map(|instruction| instruction.opcode());
@@ -908,6 +1038,38 @@ mod tests {
         assert!(Nop.change_arg(bfe!(7)).is_err());
     }
 
+    #[test]
+    fn setting_argument_of_instruction_without_argument_is_an_error() {
+        let_assert!(Err(err) = Add.with_arg(bfe!(1)));
+        assert!(InstructionError::IllegalArgument(Add, bfe!(1)) == err);
+    }
+
+    #[test]
+    fn setting_out_of_range_argument_is_an_error() {
+        assert!(Swap(ST0).with_arg(bfe!(15)).is_ok());
+        assert!(Swap(ST0).with_arg(bfe!(16)).is_err());
+        assert!(ReadMem(N1).with_arg(bfe!(6)).is_err());
+        assert!(ReadIo(N1).with_arg(bfe!(5)) == Ok(ReadIo(N5)));
+    }
+
+    #[test]
+    fn setting_argument_of_call_and_push_accepts_any_field_element() {
+        let arg = BFieldElement::new(BFieldElement::MAX);
+        assert!(Call(bfe!(0)).with_arg(arg) == Ok(Call(arg)));
+        assert!(Push(bfe!(0)).with_arg(arg) == Ok(Push(arg)));
+    }
+
+    #[test]
+    fn instruction_matchers_match_expected_instructions() {
+        assert!(InstructionMatcher::Any.matches(Halt));
+        assert!(InstructionMatcher::AnyPush.matches(Push(bfe!(42))));
+        assert!(!InstructionMatcher::AnyPush.matches(Pop(N1)));
+        assert!(InstructionMatcher::Opcode(Swap(ST0).opcode()).matches(Swap(ST5)));
+        assert!(!InstructionMatcher::Opcode(Swap(ST0).opcode()).matches(Dup(ST0)));
+        assert!(InstructionMatcher::Exact(Swap(ST1)).matches(Swap(ST1)));
+        assert!(!InstructionMatcher::Exact(Swap(ST1)).matches(Swap(ST2)));
+    }
+
     #[test]
     fn print_various_instructions() {
         println!("instruction_push: {:?}", Instruction::Push(bfe!(7)));
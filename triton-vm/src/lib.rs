@@ -148,7 +148,7 @@
 //! # use triton_vm::prelude::*;
 //! let crashing_program = triton_program!(push 2 assert halt);
 //! let vm_error = crashing_program.run([].into(), [].into()).unwrap_err();
-//! assert!(matches!(vm_error.source, InstructionError::AssertionFailed));
+//! assert!(matches!(vm_error.source, InstructionError::AssertionFailed { .. }));
 //! // inspect the VM state
 //! eprintln!("{vm_error}");
 //! ```
@@ -164,13 +164,18 @@
 pub use twenty_first;
 
 use crate::error::ProvingError;
+use crate::error::SourceProvingError;
+use crate::error::SourceVerificationError;
 use crate::prelude::*;
 
 pub mod aet;
 pub mod arithmetic_domain;
+pub mod codebook;
 pub mod config;
 pub mod error;
 pub mod example_programs;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod fri;
 pub mod instruction;
 mod ndarray_helper;
@@ -184,7 +189,11 @@ pub mod proof_item;
 pub mod proof_stream;
 pub mod stark;
 pub mod table;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 pub mod vm;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 #[cfg(test)]
 mod shared_tests;
@@ -373,6 +382,11 @@ macro_rules! triton_asm {
             concat!($fmt, " ", stringify!($label_declaration), ": "), $($args,)*; $($tail)*
         )
     };
+    (@fmt $fmt:expr, $($args:expr,)*; &$label:ident $($tail:tt)*) => {
+        $crate::triton_asm!(@fmt
+            concat!($fmt, " &", stringify!($label), " "), $($args,)*; $($tail)*
+        )
+    };
     (@fmt $fmt:expr, $($args:expr,)*; $instruction:ident $($tail:tt)*) => {
         $crate::triton_asm!(@fmt
             concat!($fmt, " ", stringify!($instruction), " "), $($args,)*; $($tail)*
@@ -508,6 +522,20 @@ pub fn prove_program(
     program: &Program,
     public_input: PublicInput,
     non_determinism: NonDeterminism,
+) -> Result<(Stark, Claim, Proof), ProvingError> {
+    // The default parameters give a (conjectured) security level of 160 bits.
+    let stark = Stark::default();
+    prove_program_with_stark(stark, program, public_input, non_determinism)
+}
+
+/// Like [`prove_program`], but using the given [`Stark`] parameters. For example, a [lower
+/// security level](Stark::with_security_level) speeds up proving, which can be helpful for
+/// testing.
+pub fn prove_program_with_stark(
+    stark: Stark,
+    program: &Program,
+    public_input: PublicInput,
+    non_determinism: NonDeterminism,
 ) -> Result<(Stark, Claim, Proof), ProvingError> {
     // Generate
     // - the witness required for proof generation, i.e., the Algebraic Execution Trace (AET), and
@@ -529,20 +557,63 @@ pub fn prove_program(
     // purposefully not used here to highlight that only a program's hash digest, not the full
     // program, is part of the claim.
     let claim = Claim {
-        program_digest: program.hash::<Tip5>(),
+        program_digest: program.hash_with_vm_hasher(),
         input: public_input.individual_tokens,
         output: public_output,
     };
 
-    // The default parameters give a (conjectured) security level of 160 bits.
-    let stark = Stark::default();
-
     // Generate the proof.
     let proof = stark.prove(&claim, &aet)?;
 
     Ok((stark, claim, proof))
 }
 
+/// Like [`prove_program`], but for a program given as Triton assembly source code. Useful for
+/// getting started, since the program is parsed, run, and proven in one go.
+///
+/// ```
+/// # use triton_vm::prelude::*;
+/// let source = "read_io 1 push 2 mul write_io 1 halt";
+/// let (stark, claim, proof) = triton_vm::prove_source(source, bfe_vec![21].into(), [].into())
+///     .unwrap();
+///
+/// assert_eq!(bfe_vec![42], claim.output);
+/// assert!(triton_vm::verify(stark, &claim, &proof));
+/// ```
+pub fn prove_source(
+    source: &str,
+    public_input: PublicInput,
+    non_determinism: NonDeterminism,
+) -> Result<(Stark, Claim, Proof), SourceProvingError> {
+    let program = Program::from_code(source)?;
+    Ok(prove_program(&program, public_input, non_determinism)?)
+}
+
+/// Verify a proof generated by [`prove_source`]. Unlike [`verify`], the [`Claim`] is derived
+/// from the program's source code, ruling out mismatches between the program and its digest.
+///
+/// ```
+/// # use triton_vm::prelude::*;
+/// let source = "read_io 1 push 2 mul write_io 1 halt";
+/// let (_, _, proof) = triton_vm::prove_source(source, bfe_vec![21].into(), [].into()).unwrap();
+///
+/// let verdict = triton_vm::verify_source(source, bfe_vec![21].into(), bfe_vec![42], &proof);
+/// assert!(verdict.is_ok());
+/// ```
+pub fn verify_source<'a>(
+    source: &'a str,
+    public_input: PublicInput,
+    public_output: Vec<BFieldElement>,
+    proof: &Proof,
+) -> Result<(), SourceVerificationError<'a>> {
+    let program = Program::from_code(source)?;
+    let claim = Claim::about_program(&program)
+        .with_input(public_input.individual_tokens)
+        .with_output(public_output);
+
+    Ok(Stark::default().verify(&claim, proof)?)
+}
+
 /// A convenience function for proving a [`Claim`] and the program that claim corresponds to.
 /// Method [`prove_program`] gives a simpler interface with less control.
 pub fn prove(
@@ -551,7 +622,7 @@ pub fn prove(
     program: &Program,
     non_determinism: NonDeterminism,
 ) -> Result<Proof, ProvingError> {
-    let program_digest = program.hash::<Tip5>();
+    let program_digest = program.hash_with_vm_hasher();
     if program_digest != claim.program_digest {
         return Err(ProvingError::ProgramDigestMismatch);
     }
@@ -622,14 +693,30 @@ mod tests {
 
         // errors
         implements_auto_traits::<error::VMError>();
+        implements_auto_traits::<error::TracingError<error::InstructionError>>();
         implements_auto_traits::<error::InstructionError>();
+        implements_auto_traits::<error::InputKind>();
+        implements_auto_traits::<error::TransitionError>();
+        implements_auto_traits::<error::StateComponent>();
         implements_auto_traits::<error::ArithmeticDomainError>();
         implements_auto_traits::<error::ProofStreamError>();
         implements_auto_traits::<error::FriSetupError>();
         implements_auto_traits::<error::FriProvingError>();
         implements_auto_traits::<error::FriValidationError>();
         implements_auto_traits::<error::ProgramDecodingError>();
+        implements_auto_traits::<error::ProgramDecompressionError>();
+        implements_auto_traits::<error::CodebookError>();
+        implements_auto_traits::<error::ProofDecodingError>();
+        implements_auto_traits::<error::InvariantViolation>();
+        implements_auto_traits::<error::ProgramValidationError>();
+        implements_auto_traits::<error::ProgramBuildingError>();
+        implements_auto_traits::<error::StackError>();
+        implements_auto_traits::<error::SubroutineExtractionError>();
+        implements_auto_traits::<error::RetraceError>();
+        implements_auto_traits::<error::ConstraintViolation>();
         implements_auto_traits::<error::ProvingError>();
+        implements_auto_traits::<error::SourceProvingError>();
+        implements_auto_traits::<error::SourceVerificationError>();
         implements_auto_traits::<error::VerificationError>();
         implements_auto_traits::<error::OpStackElementError>();
         implements_auto_traits::<error::NumberOfWordsError>();
@@ -659,6 +746,8 @@ mod tests {
         implements_auto_traits::<table::master_table::TableId>();
         implements_auto_traits::<table::master_table::MasterBaseTable>();
         implements_auto_traits::<table::master_table::MasterExtTable>();
+        implements_auto_traits::<table::master_table::TableConstraintInfo>();
+        implements_auto_traits::<table::master_table::TraceMatrices>();
         implements_auto_traits::<table::op_stack_table::OpStackTable>();
         implements_auto_traits::<table::op_stack_table::ExtOpStackTable>();
         implements_auto_traits::<table::op_stack_table::OpStackTableEntry>();
@@ -694,27 +783,49 @@ mod tests {
 
         // other
         implements_auto_traits::<aet::AlgebraicExecutionTrace>();
+        implements_auto_traits::<aet::InstructionHistogram>();
         implements_auto_traits::<aet::TableHeight>();
         implements_auto_traits::<arithmetic_domain::ArithmeticDomain>();
+        implements_auto_traits::<codebook::Codebook>();
         implements_auto_traits::<fri::Fri<Tip5>>();
         implements_auto_traits::<TypeHint>();
         implements_auto_traits::<instruction::AnInstruction<usize>>();
         implements_auto_traits::<instruction::InstructionBit>();
+        implements_auto_traits::<instruction::InstructionMatcher>();
         implements_auto_traits::<op_stack::OpStack>();
         implements_auto_traits::<op_stack::UnderflowIO>();
         implements_auto_traits::<op_stack::OpStackElement>();
         implements_auto_traits::<op_stack::NumberOfWords>();
         implements_auto_traits::<parser::ParseError>();
         implements_auto_traits::<parser::InstructionToken>();
+        implements_auto_traits::<parser::PseudoInstruction>();
+        implements_auto_traits::<parser::Warning>();
         implements_auto_traits::<profiler::VMPerformanceProfile>();
         implements_auto_traits::<program::ExecutionTraceProfile>();
         implements_auto_traits::<program::InstructionIter>();
+        implements_auto_traits::<program::ListingRow>();
+        implements_auto_traits::<program::ControlFlowGraph>();
+        implements_auto_traits::<program::BasicBlock>();
+        implements_auto_traits::<program::ControlFlowEdge>();
+        implements_auto_traits::<program::ControlFlowEdgeKind>();
+        implements_auto_traits::<program::DebugInfo>();
+        implements_auto_traits::<program::LineSpan>();
+        implements_auto_traits::<program::DigestedProgram>();
+        implements_auto_traits::<program::ProgramChunkProof>();
+        implements_auto_traits::<program::ProgramSet>();
+        implements_auto_traits::<program::ProgramBuilder>();
+        implements_auto_traits::<program::SourceLocation>();
         implements_auto_traits::<program::ProfileLine>();
         implements_auto_traits::<program::VMTableHeights>();
         implements_auto_traits::<proof_item::FriResponse>();
         implements_auto_traits::<proof_item::ProofItem>();
         implements_auto_traits::<proof_stream::ProofStream>();
+        implements_auto_traits::<stark::ProveStage>();
+        implements_auto_traits::<stark::ConstraintCheckMode>();
         implements_auto_traits::<vm::CoProcessorCall>();
+        implements_auto_traits::<vm::StateDiff>();
+        implements_auto_traits::<vm::Transition>();
+        implements_auto_traits::<vm::RamWrite>();
     }
 
     #[proptest]
@@ -783,6 +894,18 @@ mod tests {
         assert!(verdict);
     }
 
+    #[test]
+    fn lib_prove_program_with_low_security_stark() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let stark = Stark::default().with_security_level(32);
+        let (stark, claim, proof) =
+            prove_program_with_stark(stark, &program, bfe_vec![3].into(), [].into()).unwrap();
+
+        assert!(32 == stark.security_level);
+        assert!(bfe_vec![6] == claim.output);
+        assert!(verify(stark, &claim, &proof));
+    }
+
     #[test]
     fn lib_prove_with_incorrect_program_digest_gives_appropriate_error() {
         let program = triton_program!(push 1 assert halt);
@@ -806,6 +929,37 @@ mod tests {
         assert!(let ProvingError::PublicOutputMismatch = err);
     }
 
+    #[test]
+    fn proving_unparsable_source_gives_parse_error() {
+        let_assert!(Err(err) = prove_source("push", [].into(), [].into()));
+        assert!(let SourceProvingError::ParseError(_) = err);
+    }
+
+    #[test]
+    fn proving_crashing_source_gives_vm_error() {
+        let_assert!(Err(err) = prove_source("push 2 assert halt", [].into(), [].into()));
+        let_assert!(SourceProvingError::ProvingError(ProvingError::VMError(vm_error)) = err);
+        assert!(let InstructionError::AssertionFailed { .. } = vm_error.source);
+    }
+
+    #[test]
+    fn proof_for_source_verifies_only_for_same_source() {
+        let source = "push 1 write_io 1 halt";
+        let (_, claim, proof) = prove_source(source, [].into(), [].into()).unwrap();
+        let_assert!(Ok(()) = verify_source(source, [].into(), claim.output.clone(), &proof));
+
+        let other_source = "push 2 write_io 1 halt";
+        let verdict = verify_source(other_source, [].into(), claim.output, &proof);
+        let_assert!(Err(SourceVerificationError::VerificationError(_)) = verdict);
+    }
+
+    #[test]
+    fn verifying_proof_for_unparsable_source_gives_parse_error() {
+        let (_, claim, proof) = prove_source("halt", [].into(), [].into()).unwrap();
+        let verdict = verify_source("push", [].into(), claim.output, &proof);
+        let_assert!(Err(SourceVerificationError::ParseError(_)) = verdict);
+    }
+
     #[test]
     fn nested_triton_asm_interpolation() {
         let double_write = triton_asm![write_io 1; 2];
@@ -64,6 +64,25 @@ impl OpStack {
         self.stack.len()
     }
 
+    /// The number of elements in the op-stack underflow memory, _i.e._, the number of elements
+    /// not held in the op-stack registers.
+    pub fn underflow_len(&self) -> usize {
+        self.len().saturating_sub(OpStackElement::COUNT)
+    }
+
+    /// The elements held in the op-stack registers, which are directly addressable through
+    /// [`OpStackElement`]s. Like for [`stack`](Self::stack), the last element is the top of the
+    /// stack.
+    pub fn registers(&self) -> &[BFieldElement] {
+        &self.stack[self.underflow_len()..]
+    }
+
+    /// The elements in the op-stack underflow memory. Like for [`stack`](Self::stack), the first
+    /// element is the lowest element in the stack.
+    pub fn underflow_memory(&self) -> &[BFieldElement] {
+        &self.stack[..self.underflow_len()]
+    }
+
     pub(crate) fn push(&mut self, element: BFieldElement) {
         self.stack.push(element);
         self.record_underflow_io(UnderflowIO::Write);
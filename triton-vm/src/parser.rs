@@ -4,7 +4,9 @@ use std::error::Error;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::ops::Range;
 
+use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::*;
 use nom::character::complete::digit1;
@@ -13,7 +15,9 @@ use nom::error::*;
 use nom::multi::*;
 use nom::Finish;
 use nom::IResult;
+use twenty_first::prelude::tip5;
 use twenty_first::prelude::BFieldElement;
+use twenty_first::prelude::Digest;
 
 use crate::instruction::AnInstruction::*;
 use crate::instruction::LabelledInstruction;
@@ -30,6 +34,57 @@ pub struct ParseError<'a> {
     pub errors: VerboseError<&'a str>,
 }
 
+/// A pseudo-instruction is not part of Triton VM's instruction set. Instead, the parser
+/// [expands](Self::expand) it into a fixed sequence of regular instructions. The resulting
+/// [`Program`](crate::program::Program) is indistinguishable from one where the expansion is
+/// written out by hand.
+///
+/// | pseudo-instruction          | expansion                                               |
+/// |:----------------------------|:--------------------------------------------------------|
+/// | `dupn n`                    | `dup n-1`, repeated `n` times, for 1 ⩽ `n` ⩽ 16         |
+/// | `popn n`                    | `pop 5`, repeated ⌊`n`/5⌋ times, followed by `pop (n mod 5)`, for 1 ⩽ `n` ⩽ [`MAX_POPN`][max] |
+/// | `push_digest d₀ d₁ d₂ d₃ d₄` | `push d₄ push d₃ push d₂ push d₁ push d₀`               |
+///
+/// [max]: PseudoInstruction::MAX_POPN
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum PseudoInstruction {
+    /// Push the [`Digest`] such that its first element ends up on top of the stack. This is the
+    /// layout in which instruction `hash` leaves a digest on the stack and instructions like
+    /// `merkle_step` and `assert_vector` consume it. The elements are given in the order of
+    /// [`Digest::values`].
+    PushDigest(Digest),
+
+    /// Duplicate all stack elements up to and including the given one, preserving their order.
+    DupN(OpStackElement),
+
+    /// Pop the given number of elements.
+    PopN(usize),
+}
+
+impl PseudoInstruction {
+    /// The names of all pseudo-instructions, as used in Triton assembly.
+    pub const ALL_NAMES: [&'static str; 3] = ["dupn", "popn", "push_digest"];
+
+    /// The largest number of elements a single `popn` may remove. Bounding the argument bounds
+    /// the length of the expansion.
+    pub const MAX_POPN: usize = 1024;
+
+    /// The sequence of regular instructions this pseudo-instruction stands for.
+    pub fn expand(&self) -> Vec<AnInstruction<String>> {
+        match self {
+            Self::PushDigest(digest) => digest.values().into_iter().rev().map(Push).collect(),
+            Self::DupN(deepest) => vec![Dup(*deepest); usize::from(deepest) + 1],
+            Self::PopN(n) => {
+                let mut pops = vec![Pop(N5); n / 5];
+                if let Ok(remainder) = NumberOfWords::try_from(n % 5) {
+                    pops.push(Pop(remainder));
+                }
+                pops
+            }
+        }
+    }
+}
+
 /// An intermediate object for the parsing / compilation pipeline. You probably want
 /// [`LabelledInstruction`].
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -38,6 +93,7 @@ pub enum InstructionToken<'a> {
     Label(String, &'a str),
     Breakpoint(&'a str),
     TypeHint(TypeHint, &'a str),
+    PushLabelAddress(String, &'a str),
 }
 
 impl<'a> Display for ParseError<'a> {
@@ -55,6 +111,7 @@ impl<'a> InstructionToken<'a> {
             InstructionToken::Label(_, token_str) => token_str,
             InstructionToken::Breakpoint(token_str) => token_str,
             InstructionToken::TypeHint(_, token_str) => token_str,
+            InstructionToken::PushLabelAddress(_, token_str) => token_str,
         }
     }
 
@@ -65,6 +122,7 @@ impl<'a> InstructionToken<'a> {
             Label(label, _) => LabelledInstruction::Label(label.to_owned()),
             Breakpoint(_) => LabelledInstruction::Breakpoint,
             TypeHint(type_hint, _) => LabelledInstruction::TypeHint(type_hint.to_owned()),
+            PushLabelAddress(label, _) => LabelledInstruction::PushLabelAddress(label.to_owned()),
         }
     }
 }
@@ -76,6 +134,340 @@ pub fn to_labelled_instructions(instructions: &[InstructionToken]) -> Vec<Labell
         .collect()
 }
 
+/// A non-fatal issue in source code, as reported by
+/// [`Program::from_code_checked`](crate::program::Program::from_code_checked). Every warning
+/// carries the span of the offending source code in bytes, excluding trailing comments and
+/// whitespace.
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Warning {
+    /// Instruction `push` with constant P - 1 written out, where P is the field's modulus.
+    /// Likely, `-1` was intended.
+    SpelledOutMinusOne { span: Range<usize> },
+
+    /// Instructions following an unconditional `return` or `recurse` without any label in
+    /// between. They can never be executed.
+    UnreachableCode { span: Range<usize> },
+
+    /// Instructions following an unconditional `halt` without any label in between. They can
+    /// never be executed.
+    DeadCodeAfterHalt { span: Range<usize> },
+
+    /// A subroutine containing none of the instructions `return`, `recurse_or_return`, or `halt`.
+    MissingReturn { label: String, span: Range<usize> },
+
+    /// An unconditional `recurse` in a subroutine whose body leaves the op stack exactly as it
+    /// found it. Unless the subroutine exits during its first iteration, it never terminates.
+    RecurseWithoutProgress { label: String, span: Range<usize> },
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let minus_one = BFieldElement::P - 1;
+        match self {
+            Self::SpelledOutMinusOne { .. } => {
+                write!(
+                    f,
+                    "constant {minus_one} is equal to -1; consider writing `-1`"
+                )
+            }
+            Self::UnreachableCode { .. } => write!(f, "unreachable code"),
+            Self::DeadCodeAfterHalt { .. } => write!(f, "dead code after `halt`"),
+            Self::MissingReturn { label, .. } => write!(f, "subroutine `{label}` never returns"),
+            Self::RecurseWithoutProgress { label, .. } => {
+                write!(f, "subroutine `{label}` recurses without making progress")
+            }
+        }
+    }
+}
+
+impl Warning {
+    /// The span of the offending source code, in bytes.
+    pub fn span(&self) -> &Range<usize> {
+        match self {
+            Self::SpelledOutMinusOne { span }
+            | Self::UnreachableCode { span }
+            | Self::DeadCodeAfterHalt { span }
+            | Self::MissingReturn { span, .. }
+            | Self::RecurseWithoutProgress { span, .. } => span,
+        }
+    }
+}
+
+/// Find [non-fatal issues](Warning) in already [parsed](parse) source code.
+pub(crate) fn lint(code: &str, tokens: &[InstructionToken]) -> Vec<Warning> {
+    [
+        spelled_out_minus_one_warnings(code, tokens),
+        dead_code_warnings(code, tokens),
+        missing_return_warnings(code, tokens),
+        recurse_without_progress_warnings(code, tokens),
+    ]
+    .concat()
+}
+
+/// The span of the statement starting with the given token string, excluding trailing comments
+/// and whitespace.
+fn statement_span(code: &str, token_str: &str) -> Range<usize> {
+    // every token string is a suffix of the source code
+    let start = code.len() - token_str.len();
+    let end = match statement(token_str) {
+        Ok((rest, _)) => code.len() - rest.len(),
+        Err(_) => code.len(),
+    };
+    let statement = code[start..end].split("//").next().unwrap_or_default();
+    start..start + statement.trim_end().len()
+}
+
+fn spelled_out_minus_one_warnings(code: &str, tokens: &[InstructionToken]) -> Vec<Warning> {
+    let minus_one = u128::from(BFieldElement::P - 1);
+    let push_statements = tokens
+        .iter()
+        .filter_map(|token| match token {
+            InstructionToken::Instruction(Push(_), token_str) => Some(*token_str),
+            _ => None,
+        })
+        .dedup_by(|a, b| a.len() == b.len());
+
+    let mut warnings = vec![];
+    for token_str in push_statements {
+        let span = statement_span(code, token_str);
+        let is_minus_one = |word: &str| word.parse::<u128>().is_ok_and(|n| n == minus_one);
+        if code[span.clone()].split_whitespace().any(is_minus_one) {
+            warnings.push(Warning::SpelledOutMinusOne { span });
+        }
+    }
+    warnings
+}
+
+fn dead_code_warnings(code: &str, tokens: &[InstructionToken]) -> Vec<Warning> {
+    let mut warnings = vec![];
+    let mut dead_code = None;
+    let mut terminator = None;
+    let mut previous_instruction = None;
+    let mut flush = |terminator: Option<AnInstruction<String>>, dead_code: Option<Range<usize>>| {
+        let Some(span) = dead_code else {
+            return;
+        };
+        match terminator {
+            Some(Halt) => warnings.push(Warning::DeadCodeAfterHalt { span }),
+            _ => warnings.push(Warning::UnreachableCode { span }),
+        }
+    };
+
+    for token in tokens {
+        match token {
+            InstructionToken::Label(..) => {
+                flush(terminator.take(), dead_code.take());
+                previous_instruction = None;
+            }
+            InstructionToken::Instruction(instruction, token_str) => {
+                if terminator.is_some() {
+                    let span = statement_span(code, token_str);
+                    dead_code = match dead_code {
+                        Some(Range { start, .. }) => Some(start..span.end),
+                        None => Some(span),
+                    };
+                } else if previous_instruction != Some(Skiz)
+                    && matches!(instruction, Halt | Return | Recurse)
+                {
+                    terminator = Some(instruction.clone());
+                }
+                previous_instruction = Some(instruction.clone());
+            }
+            InstructionToken::PushLabelAddress(_, token_str) => {
+                if terminator.is_some() {
+                    let span = statement_span(code, token_str);
+                    dead_code = match dead_code {
+                        Some(Range { start, .. }) => Some(start..span.end),
+                        None => Some(span),
+                    };
+                }
+                previous_instruction = None;
+            }
+            InstructionToken::Breakpoint(_) | InstructionToken::TypeHint(..) => (),
+        }
+    }
+    flush(terminator, dead_code);
+
+    warnings
+}
+
+fn missing_return_warnings(code: &str, tokens: &[InstructionToken]) -> Vec<Warning> {
+    let mut warnings = vec![];
+    let mut current_subroutine = None;
+    let mut flush = |subroutine: Option<(String, &str, bool)>| {
+        let Some((label, token_str, false)) = subroutine else {
+            return;
+        };
+        let span = statement_span(code, token_str);
+        warnings.push(Warning::MissingReturn { label, span });
+    };
+
+    for token in tokens {
+        match token {
+            InstructionToken::Label(label, token_str) => {
+                flush(current_subroutine.take());
+                current_subroutine = Some((label.clone(), *token_str, false));
+            }
+            InstructionToken::Instruction(Halt | Return | RecurseOrReturn, _) => {
+                if let Some((_, _, ref mut returns)) = current_subroutine {
+                    *returns = true;
+                }
+            }
+            _ => (),
+        }
+    }
+    flush(current_subroutine);
+
+    warnings
+}
+
+fn recurse_without_progress_warnings(code: &str, tokens: &[InstructionToken]) -> Vec<Warning> {
+    let called_labels = tokens
+        .iter()
+        .filter_map(|token| match token {
+            InstructionToken::Instruction(Call(label), _) => Some(label),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    let mut warnings = vec![];
+    for (i, token) in tokens.iter().enumerate() {
+        // `recurse` jumps to the most recent call target, which is not necessarily the last label
+        let InstructionToken::Label(label, _) = token else {
+            continue;
+        };
+        if !called_labels.contains(label) {
+            continue;
+        }
+        if let Some(token_str) = recurse_without_progress(&tokens[i + 1..]) {
+            let span = statement_span(code, token_str);
+            let label = label.clone();
+            warnings.push(Warning::RecurseWithoutProgress { label, span });
+        }
+    }
+    warnings
+}
+
+/// The token string of the `recurse` ending the straight-line code at the start of the given
+/// tokens, if that `recurse` can be reached and executing the code leaves the op stack unchanged.
+/// The analysis is conservative: any instruction that might crash, has side effects, or branches
+/// in a way other than skipping an exit, makes it give up.
+fn recurse_without_progress<'a>(tokens: &[InstructionToken<'a>]) -> Option<&'a str> {
+    let mut stack = SymbolicStack::default();
+    let mut has_exit = false;
+    let mut tokens = tokens.iter().filter(|token| {
+        !matches!(
+            token,
+            InstructionToken::Breakpoint(_) | InstructionToken::TypeHint(..)
+        )
+    });
+
+    while let Some(token) = tokens.next() {
+        let (instruction, token_str) = match token {
+            InstructionToken::Instruction(instruction, token_str) => (instruction, *token_str),
+            InstructionToken::PushLabelAddress(..) => {
+                stack.apply(0, 1);
+                continue;
+            }
+            _ => return None,
+        };
+        match instruction {
+            // subroutines without any exit are reported as missing a return instead
+            Recurse => return (has_exit && stack.is_unchanged()).then_some(token_str),
+            Skiz => {
+                // only the path skipping the exit can reach `recurse`
+                if let Symbol::Constant(condition) = stack.pop() {
+                    if condition.value() != 0 {
+                        return None;
+                    }
+                }
+                let Some(InstructionToken::Instruction(Return | Halt, _)) = tokens.next() else {
+                    return None;
+                };
+                has_exit = true;
+            }
+            Nop => (),
+            Push(constant) => stack.elements.push(Symbol::Constant(*constant)),
+            Pop(n) => stack.apply(n.num_words(), 0),
+            Dup(st) => stack.dup(st.index() as usize),
+            Swap(st) => stack.swap(st.index() as usize),
+            Add | Mul | Eq => stack.apply(2, 1),
+            Split => stack.apply(1, 2),
+            XbMul => stack.apply(4, 3),
+            XxAdd | XxMul => stack.apply(6, 3),
+            Hash => stack.apply(10, 5),
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// An op stack of unknown elements, tracking where the initial elements end up.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct SymbolicStack {
+    /// The top of the stack is the last element.
+    elements: Vec<Symbol>,
+    num_initial_elements: usize,
+    num_derived_elements: usize,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Symbol {
+    /// The element initially at the given depth.
+    Initial(usize),
+
+    /// A known constant.
+    Constant(BFieldElement),
+
+    /// Some element computed during execution. Two derived elements are never considered equal,
+    /// even if they are.
+    Derived(usize),
+}
+
+impl SymbolicStack {
+    fn ensure_depth(&mut self, depth: usize) {
+        while self.elements.len() < depth {
+            let symbol = Symbol::Initial(self.num_initial_elements);
+            self.elements.insert(0, symbol);
+            self.num_initial_elements += 1;
+        }
+    }
+
+    fn pop(&mut self) -> Symbol {
+        self.ensure_depth(1);
+        self.elements.pop().unwrap()
+    }
+
+    /// Pop the given number of elements, then push the given number of unknown elements.
+    fn apply(&mut self, num_pops: usize, num_pushes: usize) {
+        self.ensure_depth(num_pops);
+        self.elements.truncate(self.elements.len() - num_pops);
+        for _ in 0..num_pushes {
+            let symbol = Symbol::Derived(self.num_derived_elements);
+            self.elements.push(symbol);
+            self.num_derived_elements += 1;
+        }
+    }
+
+    fn dup(&mut self, depth: usize) {
+        self.ensure_depth(depth + 1);
+        let symbol = self.elements[self.elements.len() - 1 - depth];
+        self.elements.push(symbol);
+    }
+
+    fn swap(&mut self, depth: usize) {
+        self.ensure_depth(depth + 1);
+        let top = self.elements.len() - 1;
+        self.elements.swap(top, top - depth);
+    }
+
+    fn is_unchanged(&self) -> bool {
+        let initial_elements = (0..self.num_initial_elements).rev().map(Symbol::Initial);
+        self.elements.iter().copied().eq(initial_elements)
+    }
+}
+
 /// Pretty-print a parse error
 ///
 /// This function wraps `convert_error()`.
@@ -142,10 +534,13 @@ fn identify_missing_labels<'a>(
 ) -> HashSet<InstructionToken<'a>> {
     let mut missing_labels = HashSet::default();
     for instruction in instructions {
-        if let InstructionToken::Instruction(Call(label), _) = instruction {
-            if !seen_labels.contains_key(label.as_str()) {
-                missing_labels.insert(instruction.to_owned());
-            }
+        let (InstructionToken::Instruction(Call(label), _)
+        | InstructionToken::PushLabelAddress(label, _)) = instruction
+        else {
+            continue;
+        };
+        if !seen_labels.contains_key(label.as_str()) {
+            missing_labels.insert(instruction.to_owned());
         }
     }
     missing_labels
@@ -183,10 +578,25 @@ type ParseResult<'input, Out> = IResult<&'input str, Out, VerboseError<&'input s
 
 pub fn tokenize(s: &str) -> ParseResult<Vec<InstructionToken>> {
     let (s, _) = comment_or_whitespace0(s)?;
-    let (s, instructions) = many0(alt((label, labelled_instruction, breakpoint, type_hint)))(s)?;
+    let (s, tokens) = many0(statement)(s)?;
     let (s, _) = context("expecting label, instruction or eof", eof)(s)?;
 
-    Ok((s, instructions))
+    Ok((s, tokens.concat()))
+}
+
+/// Parse one statement of source code. Most statements result in exactly one token, but some,
+/// like [pseudo-instructions](PseudoInstruction), result in multiple tokens.
+fn statement(s: &str) -> ParseResult<Vec<InstructionToken>> {
+    let single_token = map(
+        alt((label, labelled_instruction, breakpoint, type_hint)),
+        |token| vec![token],
+    );
+    alt((
+        push_label_address,
+        push_instructions,
+        pseudo_instruction,
+        single_token,
+    ))(s)
 }
 
 fn labelled_instruction(s_instr: &str) -> ParseResult<InstructionToken> {
@@ -194,6 +604,83 @@ fn labelled_instruction(s_instr: &str) -> ParseResult<InstructionToken> {
     Ok((s, InstructionToken::Instruction(instr, s_instr)))
 }
 
+/// Parse instruction `push` followed by one or more constants. Syntactic sugar: `push 1 2 3` is
+/// equivalent to `push 1 push 2 push 3`, _i.e._, the last constant ends up on top of the stack.
+fn push_instructions(s_instr: &str) -> ParseResult<Vec<InstructionToken>> {
+    let (s, _) = token1("push")(s_instr)?;
+    let (s, constants) = many1(field_element)(s)?;
+    let tokens = constants
+        .into_iter()
+        .map(|constant| InstructionToken::Instruction(Push(constant), s_instr))
+        .collect();
+
+    Ok((s, tokens))
+}
+
+/// Parse instruction `push` followed by the address of a label, like `push &my_label`.
+fn push_label_address(s_instr: &str) -> ParseResult<Vec<InstructionToken>> {
+    let (s, _) = token1("push")(s_instr)?;
+    let (s, _) = token0("&")(s)?;
+    let (s, label) = label_addr(s)?;
+    let (s, _) = comment_or_whitespace1(s)?;
+
+    if is_instruction_name(&label) {
+        return cut(context("label cannot be named after instruction", fail))(s);
+    }
+
+    Ok((s, vec![InstructionToken::PushLabelAddress(label, s_instr)]))
+}
+
+fn pseudo_instruction(s_instr: &str) -> ParseResult<Vec<InstructionToken>> {
+    let (s, pseudo_instruction) = alt((
+        dupn_pseudo_instruction,
+        popn_pseudo_instruction,
+        push_digest_pseudo_instruction,
+    ))(s_instr)?;
+    let tokens = pseudo_instruction
+        .expand()
+        .into_iter()
+        .map(|instruction| InstructionToken::Instruction(instruction, s_instr))
+        .collect();
+
+    Ok((s, tokens))
+}
+
+fn dupn_pseudo_instruction(s: &str) -> ParseResult<PseudoInstruction> {
+    let (s, _) = token1("dupn")(s)?;
+    let (s, n) = digit1(s)?;
+    let deepest = match n.parse::<usize>() {
+        Ok(n) if n > 0 => OpStackElement::try_from(n - 1).ok(),
+        _ => None,
+    };
+    let Some(deepest) = deepest else {
+        return context("using an out-of-bounds argument (1-16 allowed)", fail)(s);
+    };
+    let (s, _) = comment_or_whitespace1(s)?;
+
+    Ok((s, PseudoInstruction::DupN(deepest)))
+}
+
+fn popn_pseudo_instruction(s: &str) -> ParseResult<PseudoInstruction> {
+    let (s, _) = token1("popn")(s)?;
+    let (s, n) = digit1(s)?;
+    let n = n.parse::<usize>().ok();
+    let Some(n) = n.filter(|n| (1..=PseudoInstruction::MAX_POPN).contains(n)) else {
+        return context("using an out-of-bounds argument (1-1024 allowed)", fail)(s);
+    };
+    let (s, _) = comment_or_whitespace1(s)?;
+
+    Ok((s, PseudoInstruction::PopN(n)))
+}
+
+fn push_digest_pseudo_instruction(s: &str) -> ParseResult<PseudoInstruction> {
+    let (s, _) = token1("push_digest")(s)?;
+    let (s, elements) = count(field_element, tip5::DIGEST_LENGTH)(s)?;
+    let digest = Digest::new(elements.try_into().unwrap());
+
+    Ok((s, PseudoInstruction::PushDigest(digest)))
+}
+
 fn label(label_s: &str) -> ParseResult<InstructionToken> {
     let (s, addr) = label_addr(label_s)?;
     let (s, _) = whitespace0(s)?; // whitespace between label and ':' is allowed
@@ -685,6 +1172,7 @@ pub(crate) mod tests {
     use assert2::assert;
     use assert2::let_assert;
     use itertools::Itertools;
+    use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
     use rand::prelude::*;
@@ -693,6 +1181,7 @@ pub(crate) mod tests {
     use test_strategy::proptest;
     use test_strategy::Arbitrary;
     use twenty_first::bfe;
+    use twenty_first::bfe_vec;
     use twenty_first::prelude::tip5;
 
     use LabelledInstruction::Breakpoint;
@@ -894,6 +1383,60 @@ pub(crate) mod tests {
         });
     }
 
+    #[proptest]
+    fn arbitrary_whitespace_between_tokens_does_not_change_program(
+        #[strategy(vec(any::<Whitespace>(), 0..=3))] leading: Vec<Whitespace>,
+        #[strategy(vec(vec(any::<Whitespace>(), 1..=3), 9))] separators: Vec<Vec<Whitespace>>,
+        #[strategy(vec(any::<Whitespace>(), 0..=3))] trailing: Vec<Whitespace>,
+    ) {
+        let tokens = [
+            "nop", "nop", "hash", "push", "0", "skiz", "end:", "halt", "call", "end",
+        ];
+        let tokens = tokens.into_iter().map(str::to_string);
+        let separators = separators.into_iter().map(|s| s.into_iter().join(""));
+        let code = tokens.interleave(separators).join("");
+        let leading = leading.into_iter().join("");
+        let trailing = trailing.into_iter().join("");
+        let code = format!("{leading}{code}{trailing}");
+
+        parse_program_prop(TestCase {
+            input: &code,
+            expected: triton_program!(nop nop hash push 0 skiz end: halt call end),
+            message: "whitespace between tokens should not change the program",
+        });
+    }
+
+    #[test]
+    fn parse_program_on_multiple_lines() {
+        let expected = triton_program!(nop nop hash push 0 skiz end: halt call end);
+        let code = "nop\nnop\n  hash\n\tpush\n0\nskiz\n\nend:\n  halt\n  call\n    end";
+        for line_ending in ["\n", "\r\n"] {
+            let code = code.replace('\n', line_ending);
+            parse_program_prop(TestCase {
+                input: &code,
+                expected: expected.clone(),
+                message: "program without trailing line break should parse",
+            });
+
+            let code = format!("{code}{line_ending}");
+            parse_program_prop(TestCase {
+                input: &code,
+                expected: expected.clone(),
+                message: "program with trailing line break should parse",
+            });
+        }
+    }
+
+    #[test]
+    fn parse_program_with_crlf_line_endings_and_comments() {
+        let code = "// a comment\r\npush 1 // another comment\r\n\r\nfoo: pop 1\r\nhalt\r\n";
+        parse_program_prop(TestCase {
+            input: code,
+            expected: triton_program!(push 1 foo: pop 1 halt),
+            message: "CRLF line endings should not interfere with comments",
+        });
+    }
+
     #[test]
     fn parse_program_label() {
         parse_program_prop(TestCase {
@@ -1000,6 +1543,256 @@ pub(crate) mod tests {
         })
     }
 
+    #[test]
+    fn parse_program_push_with_multiple_constants() {
+        parse_program_prop(TestCase {
+            input: "push 1",
+            expected: Program::new(&[Instruction(Push(bfe!(1)))]),
+            message: "push with a single constant",
+        });
+
+        parse_program_prop(TestCase {
+            input: "push 1 2 -3",
+            expected: Program::new(&[
+                Instruction(Push(bfe!(1))),
+                Instruction(Push(bfe!(2))),
+                Instruction(Push(bfe!(-3))),
+            ]),
+            message: "push with multiple constants expands to multiple pushes",
+        });
+
+        parse_program_prop(TestCase {
+            input: "push 1 2 // comment\n 3 pop 3",
+            expected: Program::new(&[
+                Instruction(Push(bfe!(1))),
+                Instruction(Push(bfe!(2))),
+                Instruction(Push(bfe!(3))),
+                Instruction(Pop(N3)),
+            ]),
+            message: "constants of push may be separated by comments and linebreaks",
+        });
+
+        parse_program_neg_prop(NegativeTestCase {
+            input: "push",
+            expected_error: "expecting label, instruction or eof",
+            expected_error_count: 1,
+            message: "push requires at least one constant",
+        });
+
+        parse_program_neg_prop(NegativeTestCase {
+            input: "push pop 1",
+            expected_error: "expecting label, instruction or eof",
+            expected_error_count: 1,
+            message: "push requires at least one constant before the next instruction",
+        });
+    }
+
+    #[test]
+    fn push_with_multiple_constants_results_in_same_stack_as_individual_pushes() {
+        let sugared = triton_program!(push 1 2 3 write_io 3 halt);
+        let desugared = triton_program!(push 1 push 2 push 3 write_io 3 halt);
+        assert!(desugared == sugared);
+
+        let output = sugared.run([].into(), [].into()).unwrap();
+        assert!(bfe_vec![3, 2, 1] == output);
+    }
+
+    #[test]
+    fn parse_program_push_label_address() {
+        parse_program_prop(TestCase {
+            input: "push &foo halt foo: return",
+            expected: Program::new(&[
+                Instruction(Push(bfe!(3))),
+                Instruction(Halt),
+                Label("foo".to_string()),
+                Instruction(Return),
+            ]),
+            message: "pushing a label's address pushes the address",
+        });
+
+        parse_program_prop(TestCase {
+            input: "foo: push & foo pop 1 return",
+            expected: Program::new(&[
+                Label("foo".to_string()),
+                Instruction(Push(bfe!(0))),
+                Instruction(Pop(N1)),
+                Instruction(Return),
+            ]),
+            message: "whitespace between ampersand and label is allowed",
+        });
+
+        parse_program_neg_prop(NegativeTestCase {
+            input: "push &foo halt",
+            expected_error: "missing label",
+            expected_error_count: 1,
+            message: "addresses of non-existent labels cannot be pushed",
+        });
+
+        parse_program_neg_prop(NegativeTestCase {
+            input: "push &pop halt pop: return",
+            expected_error: "label cannot be named after instruction",
+            expected_error_count: 1,
+            message: "label names may not overlap with instruction names",
+        });
+    }
+
+    #[test]
+    fn pushed_label_address_is_address_of_label() {
+        let program = triton_program!(push &foo write_io 1 halt foo: return);
+        let output = program.run([].into(), [].into()).unwrap();
+        assert!(bfe_vec![5] == output);
+        assert!("foo" == program.label_for_address(5));
+    }
+
+    #[test]
+    fn parse_program_pseudo_instructions() {
+        parse_program_prop(TestCase {
+            input: "dupn 3",
+            expected: triton_program!(dup 2 dup 2 dup 2),
+            message: "dupn expands to repeated dup",
+        });
+
+        parse_program_prop(TestCase {
+            input: "dupn 16",
+            expected: Program::new(&vec![Instruction(Dup(ST15)); 16]),
+            message: "dupn can duplicate the entire accessible op stack",
+        });
+
+        parse_program_prop(TestCase {
+            input: "popn 12",
+            expected: triton_program!(pop 5 pop 5 pop 2),
+            message: "popn expands to as few pops as possible",
+        });
+
+        parse_program_prop(TestCase {
+            input: "popn 10",
+            expected: triton_program!(pop 5 pop 5),
+            message: "popn without remainder",
+        });
+
+        parse_program_prop(TestCase {
+            input: "popn: call popn",
+            expected: Program::new(&[
+                Label("popn".to_string()),
+                Instruction(Call("popn".to_string())),
+            ]),
+            message: "labels may be named after pseudo-instructions",
+        });
+    }
+
+    #[test]
+    fn parse_program_pseudo_instructions_with_illegal_arguments() {
+        for input in ["dupn 0", "dupn 17"] {
+            parse_program_neg_prop(NegativeTestCase {
+                input,
+                expected_error: "expecting label, instruction or eof",
+                expected_error_count: 1,
+                message: "dupn requires an argument between 1 and 16",
+            });
+        }
+
+        parse_program_neg_prop(NegativeTestCase {
+            input: "popn 0",
+            expected_error: "expecting label, instruction or eof",
+            expected_error_count: 1,
+            message: "popn requires a positive argument",
+        });
+    }
+
+    #[test]
+    fn parse_program_popn_with_oversized_argument() {
+        let max = PseudoInstruction::MAX_POPN;
+        let program = format!("popn {max} halt");
+        let_assert!(Ok(_) = Program::from_code(&program));
+
+        for oversized in [max + 1, 1_000_000_000_000] {
+            parse_program_neg_prop(NegativeTestCase {
+                input: &format!("popn {oversized}"),
+                expected_error: "expecting label, instruction or eof",
+                expected_error_count: 1,
+                message: "popn rejects arguments that would expand to too many instructions",
+            });
+        }
+    }
+
+    #[proptest]
+    fn push_digest_leaves_first_digest_element_on_top_of_stack(#[strategy(arb())] digest: Digest) {
+        let [d0, d1, d2, d3, d4] = digest.values();
+        let source = format!("push_digest {d0} {d1} {d2} {d3} {d4} write_io 5 halt");
+        let program = Program::from_code(&source).unwrap();
+        let output = program.run([].into(), [].into())?;
+        prop_assert_eq!(digest.values().to_vec(), output);
+    }
+
+    #[test]
+    fn pushed_digest_matches_digest_computed_by_instruction_hash() {
+        let hash_ten_elements = triton_asm!(push 1 2 3 4 5 6 7 8 9 10 hash);
+        let program = triton_program!({&hash_ten_elements} write_io 5 halt);
+        let output = program.run([].into(), [].into()).unwrap();
+        let digest = Digest::new(output.try_into().unwrap());
+
+        let [d0, d1, d2, d3, d4] = digest.values();
+        let source = format!(
+            "{} push_digest {d0} {d1} {d2} {d3} {d4} assert_vector halt",
+            hash_ten_elements.iter().join(" ")
+        );
+        let program = Program::from_code(&source).unwrap();
+        assert!(let Ok(_) = program.run([].into(), [].into()));
+    }
+
+    #[test]
+    fn push_digest_requires_exactly_five_elements() {
+        parse_program_neg_prop(NegativeTestCase {
+            input: "push_digest 1 2 3 4",
+            expected_error: "expecting label, instruction or eof",
+            expected_error_count: 1,
+            message: "push_digest requires five elements",
+        });
+
+        parse_program_neg_prop(NegativeTestCase {
+            input: "push_digest 1 2 3 4 5 6",
+            expected_error: "expecting label, instruction or eof",
+            expected_error_count: 1,
+            message: "push_digest requires five elements",
+        });
+    }
+
+    #[test]
+    fn negative_constants_are_reduced_to_canonical_representative() {
+        let program = triton_program!(push -1 push 1 add write_io 1 halt);
+        let output = program.run([].into(), [].into()).unwrap();
+        assert!(bfe_vec![0] == output);
+
+        let max_element = BFieldElement::P - 1;
+        let expected = Program::from_code(&format!("push {max_element}")).unwrap();
+        assert!(expected == Program::from_code("push -1").unwrap());
+        assert!(Program::from_code("push 0").unwrap() == Program::from_code("push -0").unwrap());
+    }
+
+    #[proptest]
+    fn negative_constant_is_additive_inverse(#[strategy(1..BFieldElement::P)] n: u64) {
+        let program = Program::from_code(&format!("push -{n}"))?;
+        let_assert!(Some(Push(constant)) = program.into_iter().next());
+        prop_assert_eq!(bfe!(0), constant + bfe!(n));
+    }
+
+    #[test]
+    fn negative_constants_must_not_exceed_field_modulus_in_magnitude() {
+        let max_magnitude = BFieldElement::P - 1;
+        let_assert!(Ok(_) = Program::from_code(&format!("push -{max_magnitude}")));
+
+        let modulus = BFieldElement::P;
+        let_assert!(Err(_) = Program::from_code(&format!("push -{modulus}")));
+        let_assert!(Err(_) = Program::from_code("push -100000000000000000000"));
+    }
+
+    #[test]
+    fn dupn_preserves_order_of_duplicated_elements() {
+        let program = triton_program!(push 1 push 2 push 3 dupn 3 write_io 5 write_io 1 halt);
+        let output = program.run([].into(), [].into()).unwrap();
+        assert!(bfe_vec![3, 2, 1, 3, 2, 1] == output);
+    }
+
     #[proptest]
     fn parse_program(#[strategy(arb())] program: Program) {
         parse(&program.to_string()).unwrap();
@@ -1,36 +1,63 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::hash::Hash;
-use std::io::Cursor;
 use std::ops::Add;
 use std::ops::AddAssign;
+use std::ops::Range;
 use std::ops::Sub;
+use std::sync::Arc;
 
 use arbitrary::Arbitrary;
 use get_size::GetSize;
 use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
+use strum::EnumCount;
 use twenty_first::prelude::*;
 
 use crate::aet::AlgebraicExecutionTrace;
+use crate::aet::TraceSink;
+use crate::codebook::Codebook;
+use crate::error::InstructionError;
+use crate::error::ProgramBuildingError;
 use crate::error::ProgramDecodingError;
+use crate::error::ProgramDecompressionError;
+use crate::error::ProgramValidationError;
+use crate::error::RetraceError;
+use crate::error::StackError;
+use crate::error::SubroutineExtractionError;
+use crate::error::TracingError;
 use crate::error::VMError;
 use crate::instruction::AnInstruction;
 use crate::instruction::Instruction;
+use crate::instruction::InstructionMatcher;
 use crate::instruction::LabelledInstruction;
 use crate::instruction::TypeHint;
+use crate::instruction::ALL_INSTRUCTIONS;
+use crate::instruction::ALL_INSTRUCTION_NAMES;
+use crate::op_stack::OpStackElement;
+use crate::parser::lint;
 use crate::parser::parse;
 use crate::parser::to_labelled_instructions;
+use crate::parser::InstructionToken;
 use crate::parser::ParseError;
+use crate::parser::PseudoInstruction;
+use crate::parser::Warning;
 use crate::profiler::profiler;
 use crate::table::hash_table::PERMUTATION_TRACE_LENGTH;
+use crate::table::ram_table::RamTableCall;
 use crate::table::u32_table::U32TableEntry;
 use crate::vm::CoProcessorCall;
+use crate::vm::RamWrite;
+use crate::vm::VMHasher;
 use crate::vm::VMState;
 
 type Result<T> = std::result::Result<T, VMError>;
@@ -167,6 +194,7 @@ impl<'a> Arbitrary<'a> for Program {
             .iter()
             .filter_map(|instruction| match instruction {
                 LabelledInstruction::Instruction(AnInstruction::Call(target)) => Some(target),
+                LabelledInstruction::PushLabelAddress(target) => Some(target),
                 _ => None,
             })
             .unique();
@@ -189,17 +217,16 @@ impl<'a> Arbitrary<'a> for Program {
 /// An `InstructionIter` loops the instructions of a `Program` by skipping duplicate placeholders.
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
 pub struct InstructionIter {
-    cursor: Cursor<Vec<Instruction>>,
+    instructions: Vec<Instruction>,
+    position: usize,
 }
 
 impl Iterator for InstructionIter {
     type Item = Instruction;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let pos = self.cursor.position() as usize;
-        let instructions = self.cursor.get_ref();
-        let instruction = *instructions.get(pos)?;
-        self.cursor.set_position((pos + instruction.size()) as u64);
+        let instruction = *self.instructions.get(self.position)?;
+        self.position += instruction.size();
 
         Some(instruction)
     }
@@ -211,18 +238,51 @@ impl IntoIterator for Program {
     type IntoIter = InstructionIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        let cursor = Cursor::new(self.instructions);
-        InstructionIter { cursor }
+        InstructionIter {
+            instructions: self.instructions,
+            position: 0,
+        }
     }
 }
 
 impl Program {
+    /// The maximum number of cycles any program can run for, limited by the width of the
+    /// [cycle counter](VMState::cycle_count). Running or tracing a program that does not halt
+    /// within this many cycles results in
+    /// [`CycleLimitExceeded`](InstructionError::CycleLimitExceeded).
+    ///
+    /// Proving might impose a lower limit; see [`Stark::max_padded_height`].
+    ///
+    /// [`Stark::max_padded_height`]: crate::stark::Stark::max_padded_height
+    pub const MAX_CYCLES: u32 = u32::MAX;
+
+    /// The number of words per leaf of the program's [Merkle tree](Self::merkle_root).
+    pub const MERKLE_CHUNK_LEN: usize = Tip5::RATE;
+
+    /// Assemble a program from a sequence of [`LabelledInstruction`]s. This is the entry point
+    /// for constructing programs programmatically, _i.e._, without going through
+    /// [Triton assembly](crate::triton_asm).
+    ///
+    /// Labels are [`LabelledInstruction::Label`]s and mark the address of the next instruction.
+    /// The target of a `call` is given by the label's name, _e.g._,
+    /// `AnInstruction::Call("foo".to_string())`, and is resolved to an absolute address here. A
+    /// label may be used before it is defined. The same holds for the addresses pushed by
+    /// [`LabelledInstruction::PushLabelAddress`].
+    ///
+    /// See also [`ProgramBuilder`], which validates the labels instead of panicking.
+    ///
+    /// # Panics
+    ///
+    /// - if any label is defined more than once
+    /// - if any `call` or `push &<label>` refers to a label that is not defined
     pub fn new(labelled_instructions: &[LabelledInstruction]) -> Self {
         let label_to_address = Self::build_label_to_address_map(labelled_instructions);
+        let labelled_instructions =
+            Self::resolve_pushed_label_addresses(labelled_instructions, &label_to_address);
         let instructions =
-            Self::turn_labels_into_addresses(labelled_instructions, &label_to_address);
+            Self::turn_labels_into_addresses(&labelled_instructions, &label_to_address);
         let address_to_label = Self::flip_map(label_to_address);
-        let (breakpoints, type_hints) = Self::extract_debug_information(labelled_instructions);
+        let (breakpoints, type_hints) = Self::extract_debug_information(&labelled_instructions);
 
         assert_eq!(instructions.len(), breakpoints.len());
         Program {
@@ -242,6 +302,10 @@ impl Program {
                 instruction_pointer += instruction.size() as u64;
                 continue;
             }
+            if let LabelledInstruction::PushLabelAddress(_) = labelled_instruction {
+                instruction_pointer += Instruction::Push(bfe!(0)).size() as u64;
+                continue;
+            }
 
             let LabelledInstruction::Label(label) = labelled_instruction else {
                 continue;
@@ -255,6 +319,23 @@ impl Program {
         label_map
     }
 
+    /// Replace every `push &<label>` with instruction `push` of the label's address.
+    fn resolve_pushed_label_addresses(
+        labelled_instructions: &[LabelledInstruction],
+        label_map: &HashMap<String, u64>,
+    ) -> Vec<LabelledInstruction> {
+        labelled_instructions
+            .iter()
+            .map(|labelled_instruction| match labelled_instruction {
+                LabelledInstruction::PushLabelAddress(label) => {
+                    let address = Self::address_for_label(label, label_map);
+                    LabelledInstruction::Instruction(AnInstruction::Push(address))
+                }
+                _ => labelled_instruction.clone(),
+            })
+            .collect()
+    }
+
     fn turn_labels_into_addresses(
         labelled_instructions: &[LabelledInstruction],
         label_to_address: &HashMap<String, u64>,
@@ -303,7 +384,7 @@ impl Program {
                     break_before_next_instruction = false;
                     address += instruction.size() as u64;
                 }
-                LabelledInstruction::Label(_) => (),
+                LabelledInstruction::Label(_) | LabelledInstruction::PushLabelAddress(_) => (),
                 LabelledInstruction::Breakpoint => break_before_next_instruction = true,
                 LabelledInstruction::TypeHint(type_hint) => match type_hints.entry(address) {
                     Entry::Occupied(mut entry) => entry.get_mut().push(type_hint.clone()),
@@ -322,6 +403,26 @@ impl Program {
             .map(|instructions| Program::new(&instructions))
     }
 
+    /// Like [`from_code`](Self::from_code), but additionally returns [warnings](Warning) about
+    /// source code that is legal but likely not what was intended.
+    pub fn from_code_checked(code: &str) -> std::result::Result<(Self, Vec<Warning>), ParseError> {
+        let tokens = parse(code)?;
+        let program = Program::new(&to_labelled_instructions(&tokens));
+        let warnings = lint(code, &tokens);
+        Ok((program, warnings))
+    }
+
+    /// Like [`from_code`](Self::from_code), but additionally returns [`DebugInfo`] relating
+    /// the program's instructions to the source code.
+    pub fn from_code_with_debug_info(
+        code: &str,
+    ) -> std::result::Result<(Self, DebugInfo), ParseError> {
+        let tokens = parse(code)?;
+        let program = Program::new(&to_labelled_instructions(&tokens));
+        let debug_info = DebugInfo::new(code, &tokens);
+        Ok((program, debug_info))
+    }
+
     pub fn labelled_instructions(&self) -> Vec<LabelledInstruction> {
         let call_targets = self.call_targets();
         let instructions_with_labels = self.instructions.iter().map(|instruction| {
@@ -364,6 +465,161 @@ impl Program {
         labelled_instructions
     }
 
+    /// A listing of the program, similar to its [`Display`] output, but with every instruction
+    /// prefixed by its address. Helps correlating instructions with the
+    /// [instruction pointer](VMState::instruction_pointer).
+    pub fn to_string_annotated(&self) -> String {
+        const ADDRESS_WIDTH: usize = 5;
+
+        let mut address = 0;
+        let mut listing = String::new();
+        for labelled_instruction in self.labelled_instructions() {
+            let line = match labelled_instruction {
+                LabelledInstruction::Instruction(instruction) => {
+                    let line = format!("{address:>ADDRESS_WIDTH$}  {instruction}");
+                    address += instruction.size();
+                    line
+                }
+                LabelledInstruction::Label(label) => format!("{label}:"),
+                other => format!("{:>ADDRESS_WIDTH$}  {other}", ""),
+            };
+            listing.push_str(&line);
+            listing.push('\n');
+        }
+
+        listing
+    }
+
+    /// The program's [disassembly](ListingRow), one row per instruction.
+    pub fn listing(&self) -> Vec<ListingRow> {
+        let mut listing = vec![];
+        let mut address = 0;
+        while let Some(instruction) = self.instructions.get(address) {
+            let words = [Some(instruction.opcode_b()), instruction.arg()]
+                .into_iter()
+                .flatten()
+                .collect();
+            let instruction = instruction
+                .map_call_address(|&call_address| self.label_for_address(call_address.value()));
+            let size = instruction.size();
+            listing.push(ListingRow {
+                address,
+                instruction,
+                words,
+            });
+            address += size;
+        }
+
+        listing
+    }
+
+    /// A semantically equivalent, normalized version of this program. Useful for recognizing
+    /// programs that differ only cosmetically, for example before caching. Concretely,
+    /// - labels are renamed to `label_0`, `label_1`, …, in order of their address,
+    /// - `nop`s are removed, unless they are the target of a `call` or follow a `skiz`,
+    /// - code following a `halt` is removed up to the next target of a `call`, unless the `halt`
+    ///   follows a `skiz`, and
+    /// - debug information, like breakpoints and type hints, is discarded.
+    ///
+    /// Running the canonicalized program produces the same output as running the original
+    /// program, for any input. Only the number of executed cycles may differ.
+    ///
+    /// If any `call` targets an address that is not the start of an instruction, the program
+    /// is returned unchanged.
+    #[must_use]
+    pub fn canonicalize(&self) -> Program {
+        let listing = self.listing();
+        let end_address = self.instructions.len();
+        let instruction_addresses = listing.iter().map(|row| row.address as u64);
+        let valid_call_targets = instruction_addresses.chain([end_address as u64]).collect();
+        let call_targets = self.call_targets();
+        if !call_targets.is_subset(&valid_call_targets) {
+            return self.clone();
+        }
+
+        let canonical_labels = call_targets
+            .into_iter()
+            .sorted()
+            .enumerate()
+            .map(|(i, address)| (address, format!("label_{i}")))
+            .collect::<HashMap<_, _>>();
+        let canonical_label = |address: u64| canonical_labels[&address].clone();
+
+        let mut labelled_instructions = vec![];
+        let mut previous_instruction = None;
+        let mut is_dead_code = false;
+        for row in listing {
+            let address = row.address as u64;
+            let is_call_target = canonical_labels.contains_key(&address);
+            if is_call_target {
+                labelled_instructions.push(LabelledInstruction::Label(canonical_label(address)));
+                is_dead_code = false;
+            }
+
+            let instruction = self.instructions[row.address];
+            let follows_skiz = previous_instruction == Some(Instruction::Skiz);
+            let is_removable_nop = instruction == Instruction::Nop && !is_call_target;
+            if !is_dead_code && (follows_skiz || !is_removable_nop) {
+                let instruction = instruction.map_call_address(|a| canonical_label(a.value()));
+                labelled_instructions.push(LabelledInstruction::Instruction(instruction));
+            }
+            if instruction == Instruction::Halt && !follows_skiz {
+                is_dead_code = true;
+            }
+            previous_instruction = Some(instruction);
+        }
+        if canonical_labels.contains_key(&(end_address as u64)) {
+            let label = canonical_label(end_address as u64);
+            labelled_instructions.push(LabelledInstruction::Label(label));
+        }
+
+        Program::new(&labelled_instructions)
+    }
+
+    /// The addresses of all instructions at which the given pattern starts to match. A pattern
+    /// matches if each of its [matchers](InstructionMatcher) matches the respective instruction of
+    /// a contiguous sequence of instructions. Matches are only considered at instruction
+    /// boundaries, and may overlap. The empty pattern matches nowhere.
+    ///
+    /// ```
+    /// # use triton_vm::prelude::*;
+    /// # use triton_vm::instruction::Instruction;
+    /// # use triton_vm::instruction::InstructionMatcher;
+    /// let program = triton_program!(push 0 add push 1 add push 0 add halt);
+    /// let push_0 = InstructionMatcher::Exact(Instruction::Push(bfe!(0)));
+    /// let add = InstructionMatcher::Exact(Instruction::Add);
+    /// assert_eq!(vec![0, 6], program.find_pattern(&[push_0, add]));
+    /// ```
+    pub fn find_pattern(&self, pattern: &[InstructionMatcher]) -> Vec<u64> {
+        if pattern.is_empty() {
+            return vec![];
+        }
+
+        self.instruction_addresses()
+            .collect_vec()
+            .windows(pattern.len())
+            .filter(|addresses| {
+                let instructions = addresses.iter().map(|&a| self.instructions[a]);
+                pattern
+                    .iter()
+                    .zip_eq(instructions)
+                    .all(|(m, i)| m.matches(i))
+            })
+            .map(|addresses| addresses[0] as u64)
+            .collect()
+    }
+
+    /// The address of every instruction, in ascending order. Skips the addresses of arguments.
+    fn instruction_addresses(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut address = 0;
+        std::iter::from_fn(move || {
+            let instruction = self.instructions.get(address)?;
+            let instruction_address = address;
+            address += instruction.size();
+            Some(instruction_address)
+        })
+    }
+
     fn call_targets(&self) -> HashSet<u64> {
         self.instructions
             .iter()
@@ -374,6 +630,255 @@ impl Program {
             .collect()
     }
 
+    /// The program's [control-flow graph](ControlFlowGraph), a building block for static
+    /// analyses of the program.
+    ///
+    /// A basic block starts at the beginning of the program, at every label, at every target of
+    /// a `call`, after every `call`, `halt`, `return`, `recurse`, and `recurse_or_return`, as
+    /// well as at both possible successors of a `skiz`.
+    pub fn control_flow_graph(&self) -> ControlFlowGraph {
+        let instruction_addresses = self.instruction_addresses().collect_vec();
+        let instruction_address_set = instruction_addresses
+            .iter()
+            .copied()
+            .collect::<HashSet<_>>();
+        let is_instruction_address = |address| instruction_address_set.contains(&address);
+
+        let mut block_starts = self
+            .address_to_label
+            .keys()
+            .map(|&address| address as usize)
+            .chain([0])
+            .collect::<HashSet<_>>();
+        for &address in &instruction_addresses {
+            let instruction = self.instructions[address];
+            let next_address = address + instruction.size();
+            match instruction {
+                Instruction::Call(target) => {
+                    block_starts.insert(target.value() as usize);
+                    block_starts.insert(next_address);
+                }
+                Instruction::Skiz => {
+                    block_starts.insert(next_address);
+                    if let Some(skippable) = self.instructions.get(next_address) {
+                        block_starts.insert(next_address + skippable.size());
+                    }
+                }
+                Instruction::Halt
+                | Instruction::Return
+                | Instruction::Recurse
+                | Instruction::RecurseOrReturn => {
+                    block_starts.insert(next_address);
+                }
+                _ => (),
+            }
+        }
+
+        let mut blocks: Vec<BasicBlock> = vec![];
+        let mut last_instruction_addresses = vec![];
+        for &address in &instruction_addresses {
+            let end = address + self.instructions[address].size();
+            match (blocks.last_mut(), last_instruction_addresses.last_mut()) {
+                (Some(block), Some(last_address)) if !block_starts.contains(&address) => {
+                    block.end = end;
+                    *last_address = address;
+                }
+                _ => {
+                    let label = self.address_to_label.get(&(address as u64)).cloned();
+                    blocks.push(BasicBlock {
+                        start: address,
+                        end,
+                        label,
+                    });
+                    last_instruction_addresses.push(address);
+                }
+            }
+        }
+
+        let mut edges = vec![];
+        for (block, &last_address) in blocks.iter().zip(&last_instruction_addresses) {
+            let source = block.start;
+            let mut add_edge = |target, kind| {
+                edges.push(ControlFlowEdge {
+                    source,
+                    target,
+                    kind,
+                })
+            };
+            let has_next = is_instruction_address(block.end);
+            match self.instructions[last_address] {
+                Instruction::Call(target) => {
+                    add_edge(target.value() as usize, ControlFlowEdgeKind::Call);
+                    if has_next {
+                        add_edge(block.end, ControlFlowEdgeKind::Return);
+                    }
+                }
+                Instruction::Skiz => {
+                    if let Some(skippable) = self.instructions.get(block.end) {
+                        add_edge(block.end, ControlFlowEdgeKind::NonZero);
+                        let skip_address = block.end + skippable.size();
+                        if is_instruction_address(skip_address) {
+                            add_edge(skip_address, ControlFlowEdgeKind::Zero);
+                        }
+                    }
+                }
+                Instruction::Halt
+                | Instruction::Return
+                | Instruction::Recurse
+                | Instruction::RecurseOrReturn => (),
+                _ if has_next => add_edge(block.end, ControlFlowEdgeKind::FallThrough),
+                _ => (),
+            }
+        }
+
+        ControlFlowGraph { blocks, edges }
+    }
+
+    /// The program's [control-flow graph](Self::control_flow_graph) in the
+    /// [DOT language](https://graphviz.org/doc/info/lang.html), for example to be rendered using
+    /// `dot -Tsvg`.
+    ///
+    /// Every node is a basic block, identified by the address of its first instruction. Hence,
+    /// node identifiers remain stable across program versions that only change other parts of
+    /// the program. A block's title is its label, if any. Edges represent
+    /// - falling through to the next block,
+    /// - the two possible successors of a `skiz`, and
+    /// - `call`s, as well as the `return` to the instruction following the `call`.
+    pub fn to_dot(&self) -> String {
+        let listing = self.listing();
+        let control_flow_graph = self.control_flow_graph();
+        let mut rows = listing.iter().peekable();
+        let mut edges = control_flow_graph.edges.iter().peekable();
+
+        let node = |address: usize| format!("ip_{address}");
+        let mut lines = vec![
+            "digraph program {".to_string(),
+            "  node [shape=box, fontname=monospace];".to_string(),
+        ];
+        for block in &control_flow_graph.blocks {
+            let title = self.label_for_address(block.start as u64);
+            let body = rows
+                .peeking_take_while(|row| row.address < block.end)
+                .map(|row| format!("{}: {}\\l", row.address, row.instruction))
+                .join("");
+            lines.push(format!(
+                "  {} [label=\"{title}:\\l{body}\"];",
+                node(block.start)
+            ));
+
+            for edge in edges.peeking_take_while(|edge| edge.source == block.start) {
+                let attributes = match edge.kind {
+                    ControlFlowEdgeKind::FallThrough => "",
+                    ControlFlowEdgeKind::Call => "label=\"call\"",
+                    ControlFlowEdgeKind::Return => "label=\"return\", style=dashed",
+                    ControlFlowEdgeKind::NonZero => "label=\"nonzero\"",
+                    ControlFlowEdgeKind::Zero => "label=\"zero\"",
+                };
+                let (source, target) = (node(edge.source), node(edge.target));
+                lines.push(format!("  {source} -> {target} [{attributes}];"));
+            }
+        }
+        lines.push("}".to_string());
+
+        lines.join("\n")
+    }
+
+    /// Extract the subroutine starting at the given label into a standalone program. The
+    /// resulting program `call`s the subroutine and `halt`s once it returns. Useful for
+    /// running, proving, or testing a subroutine in isolation.
+    ///
+    /// The subroutine ends with the first `return`, `recurse`, `recurse_or_return`, or `halt`
+    /// that does not follow a `skiz`. Any `call` inside the subroutine must target an instruction
+    /// inside the subroutine.
+    ///
+    /// ```
+    /// # use triton_vm::prelude::*;
+    /// let program = triton_program!(
+    ///     read_io 1 call double write_io 1 halt
+    ///     double: dup 0 add return
+    /// );
+    /// let subroutine = program.extract_subroutine("double").unwrap();
+    /// assert_eq!(triton_program!(call double halt double: dup 0 add return), subroutine);
+    /// ```
+    pub fn extract_subroutine(
+        &self,
+        label: &str,
+    ) -> std::result::Result<Program, SubroutineExtractionError> {
+        let Some(start) = self
+            .address_to_label
+            .iter()
+            .find_map(|(&address, name)| (name == label).then_some(address as usize))
+        else {
+            return Err(SubroutineExtractionError::UnknownLabel(label.to_string()));
+        };
+
+        let mut subroutine = vec![];
+        let mut previous_instruction = None;
+        let mut has_end = false;
+        for row in self
+            .listing()
+            .into_iter()
+            .skip_while(|row| row.address < start)
+        {
+            let instruction = self.instructions[row.address];
+            subroutine.push((row.address, instruction));
+
+            let follows_skiz = previous_instruction == Some(Instruction::Skiz);
+            let falls_through = !matches!(
+                instruction,
+                Instruction::Return
+                    | Instruction::Recurse
+                    | Instruction::RecurseOrReturn
+                    | Instruction::Halt
+            );
+            if !falls_through && !follows_skiz {
+                has_end = true;
+                break;
+            }
+            previous_instruction = Some(instruction);
+        }
+        if !has_end {
+            return Err(SubroutineExtractionError::MissingReturn(label.to_string()));
+        }
+
+        let addresses = subroutine
+            .iter()
+            .map(|&(address, _)| address as u64)
+            .collect::<HashSet<_>>();
+        let call_targets = self.call_targets();
+        let label_for_address = |address: u64| {
+            if address == start as u64 {
+                label.to_string()
+            } else {
+                self.label_for_address(address)
+            }
+        };
+
+        let mut labelled_instructions = vec![
+            LabelledInstruction::Instruction(AnInstruction::Call(label.to_string())),
+            LabelledInstruction::Instruction(AnInstruction::Halt),
+        ];
+        for (address, instruction) in subroutine {
+            if let Instruction::Call(target) = instruction {
+                let target = target.value();
+                if !addresses.contains(&target) {
+                    let error =
+                        SubroutineExtractionError::CallOutsideSubroutine { address, target };
+                    return Err(error);
+                }
+            }
+            if address == start || call_targets.contains(&(address as u64)) {
+                let label = label_for_address(address as u64);
+                labelled_instructions.push(LabelledInstruction::Label(label));
+            }
+            let instruction =
+                instruction.map_call_address(|target| label_for_address(target.value()));
+            labelled_instructions.push(LabelledInstruction::Instruction(instruction));
+        }
+
+        Ok(Program::new(&labelled_instructions))
+    }
+
     pub fn is_breakpoint(&self, address: u64) -> bool {
         let address: usize = address.try_into().unwrap();
         self.breakpoints.get(address).unwrap_or(&false).to_owned()
@@ -412,12 +917,367 @@ impl Program {
         self.instructions.is_empty()
     }
 
+    /// Release excess capacity of the program's internal buffers. Useful when keeping many
+    /// programs in memory for a long time, for example in a [`ProgramSet`].
+    pub fn shrink_to_fit(&mut self) {
+        self.instructions.shrink_to_fit();
+        self.address_to_label.shrink_to_fit();
+        self.breakpoints.shrink_to_fit();
+        self.type_hints.shrink_to_fit();
+    }
+
+    /// The arguments of all `push` instructions, in program order and including duplicates.
+    pub fn constants(&self) -> Vec<BFieldElement> {
+        self.clone()
+            .into_iter()
+            .filter_map(|instruction| match instruction {
+                Instruction::Push(constant) => Some(constant),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// For every distinct [constant](Self::constants), the number of `push` instructions using it.
+    pub fn constant_histogram(&self) -> HashMap<BFieldElement, usize> {
+        self.constants().into_iter().counts()
+    }
+
+    /// Whether the program might read [secret input](NonDeterminism), _i.e._, contains
+    /// instruction `divine` or `merkle_step`. The check is purely static: instructions that
+    /// are never executed are taken into account all the same.
+    pub fn reads_secret_input(&self) -> bool {
+        self.clone().into_iter().any(|instruction| {
+            matches!(
+                instruction,
+                Instruction::Divine(_) | Instruction::MerkleStep
+            )
+        })
+    }
+
+    /// Whether the program's output is guaranteed to depend on the public input only. Useful,
+    /// for example, for caching the result of running the program keyed by its public input.
+    ///
+    /// Apart from [secret input](Self::reads_secret_input), the initial RAM is
+    /// [non-deterministic](NonDeterminism). Any program reading from RAM is therefore considered
+    /// non-deterministic, even if it only ever reads memory it has previously written.
+    pub fn is_deterministic_in_public_input(&self) -> bool {
+        let reads_ram = |instruction: Instruction| {
+            matches!(
+                instruction,
+                Instruction::ReadMem(_)
+                    | Instruction::SpongeAbsorbMem
+                    | Instruction::XxDotStep
+                    | Instruction::XbDotStep
+            )
+        };
+
+        !self.reads_secret_input() && !self.clone().into_iter().any(reads_ram)
+    }
+
+    /// A random, well-formed program consisting of roughly `approx_len` instructions. Useful for
+    /// fuzzing and property-based testing.
+    ///
+    /// The program has a main body ending in `halt`, followed by a number of subroutines, each
+    /// ending in `return`. All labels are defined. Subroutines only ever call subroutines that
+    /// come after them, and instructions `recurse` and `recurse_or_return` are never generated.
+    /// As a result, running the program always terminates. Since the remaining instructions
+    /// and their arguments are random, the program might well crash Triton VM, but only ever
+    /// with a [`VMError`], never with a panic.
+    pub fn random(rng: &mut impl Rng, approx_len: usize) -> Self {
+        let num_subroutines = rng.gen_range(0..=approx_len / 10);
+        let block_len = approx_len / (num_subroutines + 1);
+
+        let mut labelled_instructions = Self::random_block(rng, block_len, 0..num_subroutines);
+        labelled_instructions.push(LabelledInstruction::Instruction(AnInstruction::Halt));
+        for subroutine in 0..num_subroutines {
+            let label = Self::random_subroutine_label(subroutine);
+            labelled_instructions.push(LabelledInstruction::Label(label));
+            let callees = subroutine + 1..num_subroutines;
+            labelled_instructions.extend(Self::random_block(rng, block_len, callees));
+            labelled_instructions.push(LabelledInstruction::Instruction(AnInstruction::Return));
+        }
+
+        Program::new(&labelled_instructions)
+    }
+
+    fn random_subroutine_label(subroutine: usize) -> String {
+        format!("subroutine_{subroutine}")
+    }
+
+    fn random_block(
+        rng: &mut impl Rng,
+        len: usize,
+        callees: Range<usize>,
+    ) -> Vec<LabelledInstruction> {
+        let control_flow_instructions = [
+            Instruction::Halt,
+            Instruction::Call(bfe!(0)),
+            Instruction::Return,
+            Instruction::Recurse,
+            Instruction::RecurseOrReturn,
+        ];
+        let candidates = ALL_INSTRUCTIONS
+            .into_iter()
+            .filter(|instruction| !control_flow_instructions.contains(instruction))
+            .collect_vec();
+
+        let mut block = Vec::with_capacity(len);
+        for _ in 0..len {
+            let instruction = if !callees.is_empty() && rng.gen_ratio(1, 10) {
+                let callee = rng.gen_range(callees.clone());
+                AnInstruction::Call(Self::random_subroutine_label(callee))
+            } else {
+                let instruction = candidates[rng.gen_range(0..candidates.len())];
+                let instruction = match instruction {
+                    Instruction::Push(_) => {
+                        Instruction::Push(bfe!(rng.gen_range(0..BFieldElement::P)))
+                    }
+                    _ => {
+                        let arg = bfe!(rng.gen_range(0..OpStackElement::COUNT as u64));
+                        instruction.change_arg(arg).unwrap_or(instruction)
+                    }
+                };
+                instruction.map_call_address(|_| unreachable!("calls are handled separately"))
+            };
+            block.push(LabelledInstruction::Instruction(instruction));
+        }
+        block
+    }
+
+    /// Compress the program using the given [`Codebook`], for example, to store many programs
+    /// compactly. Unlike the [encoding](BFieldCodec::encode), labels, breakpoints, and type
+    /// hints are not retained.
+    pub fn to_compressed(&self, codebook: &Codebook) -> Vec<u8> {
+        codebook.compress(self)
+    }
+
+    /// Decompress a program [compressed](Self::to_compressed) with the given [`Codebook`].
+    pub fn from_compressed(
+        bytes: &[u8],
+        codebook: &Codebook,
+    ) -> std::result::Result<Self, ProgramDecompressionError> {
+        let instructions = codebook
+            .decompress(bytes)?
+            .into_iter()
+            .flat_map(|instruction| vec![instruction; instruction.size()])
+            .collect();
+
+        Ok(Program {
+            instructions,
+            address_to_label: HashMap::default(),
+            breakpoints: vec![],
+            type_hints: HashMap::default(),
+        })
+    }
+
+    /// Whether the program is certain to execute instruction `halt` if it does not crash.
+    ///
+    /// This is a conservative heuristic, not a full control-flow analysis. Starting at the
+    /// beginning of the program, instructions are followed in order. A `call` is followed into
+    /// its destination and, if that subroutine is certain to return, execution is assumed to
+    /// continue after the `call`. An instruction directly following `skiz` might or might not be
+    /// executed: a conditional `halt` is ignored, and a conditional `call`, `return`, `recurse`, or
+    /// `recurse_or_return` makes the outcome uncertain. A `halt` that is reached this way makes
+    /// the program end in `halt`. Falling off the end of the program, recursion, and reaching
+    /// `return`, `recurse`, or `recurse_or_return` outside any subroutine do not.
+    ///
+    /// Consequently, some programs that always halt are not recognized as such, for example
+    /// if the only `halt` is conditional. No program that might loop forever or lacks a `halt`
+    /// is recognized as ending in one.
+    pub fn ends_in_halt(&self) -> bool {
+        let mut subroutine_outcomes = HashMap::new();
+        self.path_outcome(0, &mut subroutine_outcomes) == PathOutcome::Halts
+    }
+
+    /// The certain outcome of executing the program from the given address, if any. A
+    /// subroutine's outcome is `None` while it is being analyzed, which identifies recursion.
+    fn path_outcome(
+        &self,
+        start: usize,
+        subroutine_outcomes: &mut HashMap<usize, Option<PathOutcome>>,
+    ) -> PathOutcome {
+        let mut instruction_pointer = start;
+        let mut is_conditional = false;
+        while let Some(&instruction) = self.instructions.get(instruction_pointer) {
+            let is_control_flow = matches!(
+                instruction,
+                Instruction::Call(_)
+                    | Instruction::Return
+                    | Instruction::Recurse
+                    | Instruction::RecurseOrReturn
+            );
+            if is_conditional && is_control_flow {
+                return PathOutcome::Unknown;
+            }
+            if !is_conditional {
+                match instruction {
+                    Instruction::Halt => return PathOutcome::Halts,
+                    Instruction::Return => return PathOutcome::Returns,
+                    Instruction::Recurse | Instruction::RecurseOrReturn => {
+                        return PathOutcome::Unknown
+                    }
+                    Instruction::Call(address) => {
+                        let Ok(address) = usize::try_from(address.value()) else {
+                            return PathOutcome::Unknown;
+                        };
+                        match self.subroutine_outcome(address, subroutine_outcomes) {
+                            PathOutcome::Returns => (),
+                            outcome => return outcome,
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            is_conditional = instruction == Instruction::Skiz;
+            instruction_pointer += instruction.size();
+        }
+        PathOutcome::Unknown
+    }
+
+    fn subroutine_outcome(
+        &self,
+        address: usize,
+        subroutine_outcomes: &mut HashMap<usize, Option<PathOutcome>>,
+    ) -> PathOutcome {
+        match subroutine_outcomes.get(&address) {
+            Some(Some(outcome)) => return *outcome,
+            Some(None) => return PathOutcome::Unknown,
+            None => (),
+        }
+        subroutine_outcomes.insert(address, None);
+        let outcome = self.path_outcome(address, subroutine_outcomes);
+        subroutine_outcomes.insert(address, Some(outcome));
+        outcome
+    }
+
+    /// Perform static sanity checks on the program. Currently, this only checks that the
+    /// program [ends in `halt`](Self::ends_in_halt). Since that check is conservative, a
+    /// program failing validation might still run to completion.
+    pub fn validate(&self) -> std::result::Result<(), ProgramValidationError> {
+        if !self.ends_in_halt() {
+            return Err(ProgramValidationError::MissingHalt);
+        }
+        Ok(())
+    }
+
+    /// Statically check that no execution of the program can
+    /// [underflow](InstructionError::OpStackTooShallow) the op stack, independent of any input.
+    ///
+    /// The op stack's height is tracked along every path through the program. Since the
+    /// outcome of a `skiz` is not known statically, both of its branches are considered.
+    /// Subroutines are analyzed in the context of each `call`.
+    ///
+    /// # Errors
+    ///
+    /// - [`StackError::Underflow`] if the op stack underflows on a path without any branching,
+    ///   _i.e._, for every input that reaches the underflowing instruction.
+    /// - [`StackError::Unknown`] if the analysis cannot rule out an underflow. This happens if the
+    ///   op stack underflows on some branch that might not be feasible, if a loop changes the
+    ///   height of the op stack, or if subroutines are recursive through `call`.
+    pub fn check_stack_balance(&self) -> std::result::Result<(), StackError> {
+        StackBalanceAnalysis::new(self).check()
+    }
+
     /// Produces the program's canonical hash digest for the given `AlgebraicHasher`.
     pub fn hash<H: AlgebraicHasher>(&self) -> Digest {
         // not encoded using `BFieldCodec` because that would prepend the length
         H::hash_varlen(&self.to_bwords())
     }
 
+    /// Like [`hash`](Self::hash), but absorbs the program's words into the sponge as they are
+    /// produced, without first collecting them into a vector. The resulting digest is identical.
+    /// Saves memory for very large programs.
+    pub fn hash_streaming<H: AlgebraicHasher>(&self) -> Digest {
+        let mut instruction_pointer = 0;
+        let instructions = std::iter::from_fn(|| {
+            let instruction = self.instructions.get(instruction_pointer)?;
+            instruction_pointer += instruction.size();
+            Some(instruction)
+        });
+        let words = instructions.flat_map(|instruction| {
+            [Some(instruction.opcode_b()), instruction.arg()]
+                .into_iter()
+                .flatten()
+        });
+
+        // padding is one 1, then as many zeros as necessary: [1, 0, 0, …]
+        let padded_length = (self.len_bwords() + 1).next_multiple_of(tip5::RATE);
+        let padding = [bfe!(1)].into_iter().chain(std::iter::repeat(bfe!(0)));
+        let padded_words = words.chain(padding).take(padded_length);
+
+        let mut sponge = H::init();
+        for chunk in &padded_words.chunks(tip5::RATE) {
+            let chunk = chunk.collect_vec().try_into().unwrap();
+            sponge.absorb(chunk);
+        }
+        let [d0, d1, d2, d3, d4, ..] = sponge.squeeze();
+
+        Digest::new([d0, d1, d2, d3, d4])
+    }
+
+    /// Produces the program's canonical hash digest using the [hash function native to Triton
+    /// VM](VMHasher). This is the digest Triton VM uses for [program attestation], for example
+    /// in a [`Claim`](crate::Claim). Prefer this method over [`hash`](Self::hash).
+    ///
+    /// [program attestation]: https://triton-vm.org/spec/program-attestation.html
+    pub fn hash_with_vm_hasher(&self) -> Digest {
+        self.hash::<VMHasher>()
+    }
+
+    /// The root of a Merkle tree over the program. The tree's leaves are the digests of
+    /// consecutive chunks of [`MERKLE_CHUNK_LEN`](Self::MERKLE_CHUNK_LEN) words of the program's
+    /// [encoding](Self::to_bwords); the last chunk might be shorter. If required, the leaves are
+    /// padded with the default [`Digest`] to make their number a power of two.
+    ///
+    /// Unlike the program's [`hash`](Self::hash), this commitment allows proving that some part
+    /// of the program is at some address without disclosing the rest of the program. See
+    /// [`chunk_proof`](Self::chunk_proof).
+    pub fn merkle_root<H: AlgebraicHasher>(&self) -> Digest {
+        let (tree, _) = self.merkle_tree::<H>();
+        tree.root()
+    }
+
+    /// Prove that the chunk with the given index is part of the program committed to by its
+    /// [`merkle_root`](Self::merkle_root). Returns `None` if the program has no such chunk.
+    ///
+    /// The chunk with index `i` starts at address `i·`[`MERKLE_CHUNK_LEN`](Self::MERKLE_CHUNK_LEN).
+    /// Since chunks are not aligned with instructions, the chunk's first word might be the
+    /// argument of an instruction in the preceding chunk; see [`ProgramChunkProof`].
+    pub fn chunk_proof<H: AlgebraicHasher>(&self, chunk_index: usize) -> Option<ProgramChunkProof> {
+        let words = self.to_bwords();
+        let chunk = words.chunks(Self::MERKLE_CHUNK_LEN).nth(chunk_index)?;
+        let (tree, tree_height) = self.merkle_tree::<H>();
+        let authentication_path = tree.authentication_structure(&[chunk_index]).ok()?;
+
+        let proof = ProgramChunkProof {
+            chunk_index,
+            chunk: chunk.to_vec(),
+            tree_height,
+            authentication_path,
+        };
+        Some(proof)
+    }
+
+    fn merkle_tree<H: AlgebraicHasher>(&self) -> (MerkleTree<H>, usize) {
+        let words = self.to_bwords();
+        let mut leaves = words
+            .chunks(Self::MERKLE_CHUNK_LEN)
+            .map(H::hash_varlen)
+            .collect_vec();
+        leaves.resize(leaves.len().next_power_of_two(), Digest::default());
+
+        let tree_height = leaves.len().ilog2() as usize;
+        let tree = CpuParallel::from_digests(&leaves).expect("number of leaves is a power of two");
+        (tree, tree_height)
+    }
+
+    /// Compute the program's [digest](Self::hash_with_vm_hasher) once and keep it alongside the
+    /// program. Useful if the digest is needed repeatedly, for example as a cache key.
+    #[must_use]
+    pub fn with_cached_digest(self) -> DigestedProgram {
+        DigestedProgram::from(self)
+    }
+
     /// Run Triton VM on the [`Program`] with the given public input and non-determinism.
     /// If an error is encountered, the returned [`VMError`] contains the [`VMState`] at the point
     /// of execution failure.
@@ -438,112 +1298,1087 @@ impl Program {
         Ok(state.public_output)
     }
 
-    /// Trace the execution of a [`Program`]. That is, [`run`][run] the [`Program`] and additionally
-    /// record that part of every encountered state that is necessary for proving correct execution.
-    /// If execution  succeeds, returns
-    /// 1. an [`AlgebraicExecutionTrace`], and
-    /// 1. the output of the program.
-    ///
-    /// See also [`run`][run] and [`profile`][profile].
+    /// Like [`run`][run], but aborts with
+    /// [`CycleLimitExceeded`](InstructionError::CycleLimitExceeded) if the program does not halt
+    /// within `max_cycles` cycles. Useful for running untrusted programs, which might never
+    /// halt. See also [`trace_execution_bounded`](Self::trace_execution_bounded).
     ///
     /// [run]: Self::run
-    /// [profile]: Self::profile
-    pub fn trace_execution(
+    pub fn run_bounded(
         &self,
         public_input: PublicInput,
         non_determinism: NonDeterminism,
-    ) -> Result<(AlgebraicExecutionTrace, Vec<BFieldElement>)> {
-        profiler!(start "trace execution" ("gen"));
-        let state = VMState::new(self, public_input, non_determinism);
-        let (aet, terminal_state) = self.trace_execution_of_state(state)?;
-        profiler!(stop "trace execution");
-        Ok((aet, terminal_state.public_output))
+        max_cycles: u32,
+    ) -> Result<Vec<BFieldElement>> {
+        let mut state = VMState::new(self, public_input, non_determinism);
+        while !state.halting {
+            if state.cycle_count >= max_cycles {
+                let err = InstructionError::CycleLimitExceeded(max_cycles);
+                return Err(VMError::new(err, state));
+            }
+            if let Err(err) = state.step() {
+                return Err(VMError::new(err, state));
+            }
+        }
+        Ok(state.public_output)
     }
 
-    /// Trace the execution of a [`Program`] from a given [`VMState`]. Consider
-    /// using [`trace_execution`][Self::trace_execution], unless you know this is
-    /// what you want.
+    /// Like [`run`][run], but additionally returns a chronological log of every write to RAM.
+    /// Helps answering "when did this RAM cell change?" without recording every [`VMState`].
     ///
-    /// Returns the [`AlgebraicExecutionTrace`] and the terminal [`VMState`] if
-    /// execution succeeds.
-    ///
-    /// # Panics
-    ///
-    /// - if the given [`VMState`] is not about to `self`
-    /// - if the given [`VMState`] is incorrectly initialized
-    pub fn trace_execution_of_state(
+    /// [run]: Self::run
+    pub fn run_with_ram_log(
         &self,
-        mut state: VMState,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> Result<(Vec<BFieldElement>, Vec<RamWrite>)> {
+        let mut state = VMState::new(self, public_input, non_determinism);
+        let mut ram = state.ram.clone();
+        let mut ram_log = vec![];
+        while !state.halting {
+            let co_processor_calls = match state.step() {
+                Ok(calls) => calls,
+                Err(err) => return Err(VMError::new(err, state)),
+            };
+            for call in co_processor_calls {
+                let CoProcessorCall::RamCall(RamTableCall {
+                    clk,
+                    ram_pointer,
+                    ram_value,
+                    is_write: true,
+                }) = call
+                else {
+                    continue;
+                };
+                let old_value = ram.insert(ram_pointer, ram_value).unwrap_or(bfe!(0));
+                ram_log.push(RamWrite {
+                    cycle: clk,
+                    address: ram_pointer,
+                    old_value,
+                    new_value: ram_value,
+                });
+            }
+        }
+        Ok((state.public_output, ram_log))
+    }
+
+    /// Like [`run`][run], but the public input is read from the given [`InputSource`] only when
+    /// needed, _i.e._, when instruction `read_io` is executed. If the source is exhausted, the
+    /// error is the same as for running out of [`PublicInput`].
+    ///
+    /// [run]: Self::run
+    pub fn run_with_input_source(
+        &self,
+        mut public_input: impl InputSource,
+        non_determinism: NonDeterminism,
+    ) -> Result<Vec<BFieldElement>> {
+        let mut state = VMState::new(self, PublicInput::default(), non_determinism);
+        while !state.halting {
+            state.pull_public_input_from(&mut public_input);
+            if let Err(err) = state.step() {
+                return Err(VMError::new(err, state));
+            }
+        }
+        Ok(state.public_output)
+    }
+
+    /// Like [`run`][run], but the secret input is pulled from the given iterator only when
+    /// needed, _i.e._, when instruction `divine` is executed. This avoids materializing secret
+    /// input the program never reads. If the iterator is exhausted, the error is the same as for
+    /// running out of secret input in [`NonDeterminism`].
+    ///
+    /// No secret digests and no initial RAM are available to the program.
+    ///
+    /// [run]: Self::run
+    pub fn run_with_secret_source<I: Iterator<Item = BFieldElement>>(
+        &self,
+        public_input: PublicInput,
+        mut secret_input: I,
+    ) -> Result<Vec<BFieldElement>> {
+        let mut secret_source = || secret_input.next();
+        let mut state = VMState::new(self, public_input, NonDeterminism::default());
+        while !state.halting {
+            state.pull_secret_input_from(&mut secret_source);
+            if let Err(err) = state.step() {
+                return Err(VMError::new(err, state));
+            }
+        }
+        Ok(state.public_output)
+    }
+
+    /// Shrink secret input that makes the program crash, keeping only what is needed to
+    /// reproduce the crash. A crash is reproduced if the same kind of [`InstructionError`]
+    /// happens at the same instruction pointer, no later than the original crash. This is
+    /// [delta debugging](https://en.wikipedia.org/wiki/Delta_debugging): sublists of the secret
+    /// input are removed for as long as the crash reproduces, after which individual elements
+    /// are set to zero where possible.
+    ///
+    /// The number of attempted reproductions is bounded, in which case the returned secret
+    /// input might not be minimal. If the program does not crash with the given inputs within
+    /// `max_cycles` cycles, the secret input is returned unchanged. In particular, exceeding the
+    /// cycle limit is not considered a crash.
+    pub fn minimize_failing_input(
+        &self,
+        public_input: PublicInput,
+        failing_secret: Vec<BFieldElement>,
+        max_cycles: u32,
+    ) -> Vec<BFieldElement> {
+        const MAX_NUM_RUNS: usize = 1 << 12;
+
+        let crash = |secret: &[BFieldElement], max_cycles| {
+            let non_determinism = NonDeterminism::new(secret);
+            let err = self
+                .run_bounded(public_input.clone(), non_determinism, max_cycles)
+                .err()?;
+            if let InstructionError::CycleLimitExceeded(_) = err.source {
+                return None;
+            }
+            let variant = std::mem::discriminant(&err.source);
+            Some((
+                variant,
+                err.vm_state.instruction_pointer,
+                err.vm_state.cycle_count,
+            ))
+        };
+        let Some((variant, ip, crash_cycle)) = crash(&failing_secret, max_cycles) else {
+            return failing_secret;
+        };
+        let max_cycles = crash_cycle.saturating_add(1);
+        let reproduces = |secret: &[BFieldElement]| {
+            crash(secret, max_cycles).is_some_and(|(v, i, _)| v == variant && i == ip)
+        };
+        let mut num_runs = 1;
+
+        let mut secret = failing_secret;
+        let mut num_chunks = 2;
+        while !secret.is_empty() && num_runs < MAX_NUM_RUNS {
+            let chunk_len = secret.len().div_ceil(num_chunks);
+            let mut reduced_secret = None;
+            for start in (0..secret.len()).step_by(chunk_len) {
+                if num_runs >= MAX_NUM_RUNS {
+                    break;
+                }
+                let end = secret.len().min(start + chunk_len);
+                let candidate = [&secret[..start], &secret[end..]].concat();
+                num_runs += 1;
+                if reproduces(&candidate) {
+                    reduced_secret = Some(candidate);
+                    break;
+                }
+            }
+
+            if let Some(reduced_secret) = reduced_secret {
+                secret = reduced_secret;
+                num_chunks = 2.max(num_chunks - 1);
+            } else if chunk_len > 1 {
+                num_chunks = secret.len().min(2 * num_chunks);
+            } else {
+                break;
+            }
+        }
+
+        let non_zero_indices = secret.iter().positions(|&e| e != bfe!(0)).collect_vec();
+        for i in non_zero_indices {
+            if num_runs >= MAX_NUM_RUNS {
+                break;
+            }
+            let mut candidate = secret.clone();
+            candidate[i] = bfe!(0);
+            num_runs += 1;
+            if reproduces(&candidate) {
+                secret = candidate;
+            }
+        }
+
+        secret
+    }
+
+    /// Trace the execution of a [`Program`]. That is, [`run`][run] the [`Program`] and additionally
+    /// record that part of every encountered state that is necessary for proving correct execution.
+    /// If execution  succeeds, returns
+    /// 1. an [`AlgebraicExecutionTrace`], and
+    /// 1. the output of the program.
+    ///
+    /// See also [`run`][run] and [`profile`][profile]. To additionally record every call to a
+    /// co-processor, see [`trace_execution_with_co_processor_calls`][co_proc].
+    ///
+    /// [run]: Self::run
+    /// [profile]: Self::profile
+    /// [co_proc]: Self::trace_execution_with_co_processor_calls
+    pub fn trace_execution(
+        &self,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> Result<(AlgebraicExecutionTrace, Vec<BFieldElement>)> {
+        profiler!(start "trace execution" ("gen"));
+        let state = VMState::new(self, public_input, non_determinism);
+        let (aet, terminal_state) = self.trace_execution_of_state(state)?;
+        profiler!(stop "trace execution");
+        Ok((aet, terminal_state.public_output))
+    }
+
+    /// Like [`trace_execution`][trace_execution], but the returned [`AlgebraicExecutionTrace`]
+    /// additionally [records every call to a co-processor][calls].
+    ///
+    /// [trace_execution]: Self::trace_execution
+    /// [calls]: AlgebraicExecutionTrace::co_processor_calls
+    pub fn trace_execution_with_co_processor_calls(
+        &self,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> Result<(AlgebraicExecutionTrace, Vec<BFieldElement>)> {
+        profiler!(start "trace execution" ("gen"));
+        let state = VMState::new(self, public_input, non_determinism);
+        let mut aet = AlgebraicExecutionTrace::new(self.clone()).with_co_processor_call_recording();
+        let terminal_state =
+            Self::trace_execution_of_state_into(state, None, &mut no_input(), &mut aet)?;
+        profiler!(stop "trace execution");
+        Ok((aet, terminal_state.public_output))
+    }
+
+    /// Like [`trace_execution`][trace_execution], but aborts with
+    /// [`CycleLimitExceeded`](InstructionError::CycleLimitExceeded) if the program
+    /// does not halt within `max_cycles` cycles. Useful for executing untrusted programs, since
+    /// the size of the [`AlgebraicExecutionTrace`] is bounded. If the limit is exceeded, the
+    /// partial trace is discarded.
+    ///
+    /// [trace_execution]: Self::trace_execution
+    pub fn trace_execution_bounded(
+        &self,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+        max_cycles: u32,
+    ) -> Result<(AlgebraicExecutionTrace, Vec<BFieldElement>)> {
+        profiler!(start "trace execution" ("gen"));
+        let state = VMState::new(self, public_input, non_determinism);
+        let (aet, terminal_state) =
+            self.trace_execution_of_state_up_to(state, Some(max_cycles), &mut no_input())?;
+        profiler!(stop "trace execution");
+        Ok((aet, terminal_state.public_output))
+    }
+
+    /// Like [`trace_execution`][trace_execution], but the public input is read from the given
+    /// [`InputSource`] only when needed. See also
+    /// [`run_with_input_source`][run_with_input_source].
+    ///
+    /// [trace_execution]: Self::trace_execution
+    /// [run_with_input_source]: Self::run_with_input_source
+    pub fn trace_execution_with_input_source(
+        &self,
+        mut public_input: impl InputSource,
+        non_determinism: NonDeterminism,
+    ) -> Result<(AlgebraicExecutionTrace, Vec<BFieldElement>)> {
+        profiler!(start "trace execution" ("gen"));
+        let state = VMState::new(self, PublicInput::default(), non_determinism);
+        let (aet, terminal_state) =
+            self.trace_execution_of_state_up_to(state, None, &mut public_input)?;
+        profiler!(stop "trace execution");
+        Ok((aet, terminal_state.public_output))
+    }
+
+    /// Like [`trace_execution`][trace_execution], but every recorded state and co-processor
+    /// call is handed to the given [`TraceSink`] as soon as it is produced, instead of being
+    /// collected into an [`AlgebraicExecutionTrace`]. Returns the public output.
+    ///
+    /// [trace_execution]: Self::trace_execution
+    pub fn trace_execution_into<S: TraceSink>(
+        &self,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+        sink: &mut S,
+    ) -> std::result::Result<Vec<BFieldElement>, TracingError<S::Error>> {
+        profiler!(start "trace execution" ("gen"));
+        let state = VMState::new(self, public_input, non_determinism);
+        let terminal_state =
+            Self::trace_execution_of_state_into(state, None, &mut no_input(), sink)?;
+        profiler!(stop "trace execution");
+        Ok(terminal_state.public_output)
+    }
+
+    /// Trace the execution of a [`Program`] from a given [`VMState`]. Consider
+    /// using [`trace_execution`][Self::trace_execution], unless you know this is
+    /// what you want.
+    ///
+    /// Returns the [`AlgebraicExecutionTrace`] and the terminal [`VMState`] if
+    /// execution succeeds.
+    ///
+    /// # Panics
+    ///
+    /// - if the given [`VMState`] is not about to `self`
+    /// - if the given [`VMState`] is incorrectly initialized
+    pub fn trace_execution_of_state(
+        &self,
+        state: VMState,
+    ) -> Result<(AlgebraicExecutionTrace, VMState)> {
+        self.trace_execution_of_state_up_to(state, None, &mut no_input())
+    }
+
+    /// Re-trace the execution of a [`Program`], re-using the given states up to
+    /// `changed_cycle`. Useful after editing the state at `changed_cycle`, for
+    /// example, its RAM, since states before the edit don't need to be recomputed.
+    ///
+    /// Here, a trace is the sequence of every [`VMState`] from the state before
+    /// the first instruction up to and including the terminal state. The
+    /// `prior` states must follow this convention, except that the sequence may
+    /// be cut short. In particular, the entire trace is computed by passing only
+    /// the initial state and a `changed_cycle` of 0.
+    ///
+    /// The returned trace is identical to running the program from the state
+    /// at `changed_cycle`, prefixed by the prior states before `changed_cycle`.
+    ///
+    /// Re-tracing aborts with
+    /// [`CycleLimitExceeded`](InstructionError::CycleLimitExceeded) if the
+    /// program does not halt within `max_cycles` cycles, counted from the start
+    /// of the execution.
+    ///
+    /// # Errors
+    ///
+    /// - if `changed_cycle` is not an index into `prior`
+    /// - if Triton VM crashes or exceeds the cycle limit
+    ///
+    /// # Panics
+    ///
+    /// - if the state at `changed_cycle` is not about to `self`
+    pub fn retrace_from(
+        &self,
+        prior: &[VMState],
+        changed_cycle: usize,
+        max_cycles: u32,
+    ) -> std::result::Result<Vec<VMState>, RetraceError> {
+        let Some(state) = prior.get(changed_cycle) else {
+            let num_prior_states = prior.len();
+            return Err(RetraceError::ChangedCycleOutOfBounds {
+                changed_cycle,
+                num_prior_states,
+            });
+        };
+        let mut state = state.clone();
+        assert_eq!(self.instructions, state.program);
+
+        let mut trace = prior[..=changed_cycle].to_vec();
+        while !state.halting {
+            if state.cycle_count >= max_cycles {
+                let err = InstructionError::CycleLimitExceeded(max_cycles);
+                return Err(VMError::new(err, state).into());
+            }
+            if let Err(err) = state.step() {
+                return Err(VMError::new(err, state).into());
+            }
+            trace.push(state.clone());
+        }
+
+        Ok(trace)
+    }
+
+    fn trace_execution_of_state_up_to(
+        &self,
+        state: VMState,
+        max_cycles: Option<u32>,
+        public_input: &mut impl InputSource,
     ) -> Result<(AlgebraicExecutionTrace, VMState)> {
         let mut aet = AlgebraicExecutionTrace::new(self.clone());
         assert_eq!(self.instructions, state.program);
         assert_eq!(self.len_bwords(), aet.instruction_multiplicities.len());
+        let terminal_state =
+            Self::trace_execution_of_state_into(state, max_cycles, public_input, &mut aet)?;
+
+        Ok((aet, terminal_state))
+    }
+
+    fn trace_execution_of_state_into<S: TraceSink>(
+        mut state: VMState,
+        max_cycles: Option<u32>,
+        public_input: &mut impl InputSource,
+        sink: &mut S,
+    ) -> std::result::Result<VMState, TracingError<S::Error>> {
+        let max_cycles = max_cycles.unwrap_or(Self::MAX_CYCLES);
+        while !state.halting {
+            if state.cycle_count >= max_cycles {
+                let err = InstructionError::CycleLimitExceeded(max_cycles);
+                return Err(VMError::new(err, state).into());
+            }
+            state.pull_public_input_from(public_input);
+            if let Err(source) = sink.record_state(&state) {
+                let vm_state = Box::new(state);
+                return Err(TracingError::SinkFailure { source, vm_state });
+            };
+            let co_processor_calls = match state.step() {
+                Ok(calls) => calls,
+                Err(err) => return Err(VMError::new(err, state).into()),
+            };
+            for call in co_processor_calls {
+                if let Err(source) = sink.record_co_processor_call(call) {
+                    let vm_state = Box::new(state);
+                    return Err(TracingError::SinkFailure { source, vm_state });
+                }
+            }
+        }
+
+        Ok(state)
+    }
+
+    /// Run Triton VM with the given public and secret input, recording the
+    /// influence of a callable block of instructions on the
+    /// [`AlgebraicExecutionTrace`]. For example, this can be used to identify the
+    /// number of clock cycles spent in some block of instructions, or how many rows
+    /// it contributes to the U32 Table.
+    ///
+    /// See also [`run`][run] and [`trace_execution`][trace_execution].
+    ///
+    /// [run]: Self::run
+    /// [trace_execution]: Self::trace_execution
+    pub fn profile(
+        &self,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> Result<(Vec<BFieldElement>, ExecutionTraceProfile)> {
+        let mut profiler = ExecutionTraceProfiler::new(self.instructions.len());
+        let mut state = VMState::new(self, public_input, non_determinism);
+        let mut previous_jump_stack_len = state.jump_stack.len();
+        while !state.halting {
+            if let Ok(Instruction::Call(address)) = state.current_instruction() {
+                let label = self.label_for_address(address.value());
+                profiler.enter_span(label);
+            }
+
+            match state.step() {
+                Ok(calls) => profiler.handle_co_processor_calls(calls),
+                Err(err) => return Err(VMError::new(err, state)),
+            };
+
+            if state.jump_stack.len() < previous_jump_stack_len {
+                profiler.exit_span();
+            }
+            previous_jump_stack_len = state.jump_stack.len();
+        }
+
+        Ok((state.public_output, profiler.finish()))
+    }
+
+    /// The label for the given address, or a deterministic, unique substitute if no label is found.
+    pub fn label_for_address(&self, address: u64) -> String {
+        // Uniqueness of the label is relevant for printing and subsequent parsing:
+        // Parsing fails on duplicate labels.
+        self.address_to_label
+            .get(&address)
+            .cloned()
+            .unwrap_or_else(|| format!("address_{address}"))
+    }
+
+    /// Candidates for completing a word of Triton assembly referring to this program: the
+    /// mnemonics of all [instructions](Instruction) and
+    /// [pseudo-instructions](PseudoInstruction), followed by all of the program's labels.
+    /// Either part is sorted lexicographically.
+    pub fn completion_candidates(&self) -> Vec<String> {
+        let mnemonics = ALL_INSTRUCTION_NAMES
+            .into_iter()
+            .chain(PseudoInstruction::ALL_NAMES)
+            .sorted();
+        let labels = self.address_to_label.values().map(String::as_str).sorted();
+
+        mnemonics.chain(labels).map(str::to_string).collect()
+    }
+}
+
+/// The certain outcome of executing (part of) a [`Program`]. See [`Program::ends_in_halt`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum PathOutcome {
+    Halts,
+    Returns,
+    Unknown,
+}
+
+/// Abstract interpretation of a [`Program`] in terms of the op stack's height, relative to its
+/// initial height. See [`Program::check_stack_balance`].
+#[derive(Debug)]
+struct StackBalanceAnalysis<'program> {
+    program: &'program Program,
+
+    /// For a subroutine's entry address, the op stack height upon entering it, and whether
+    /// entering is certain: all possible op stack heights upon returning, and whether returning
+    /// is certain.
+    subroutine_exits: HashMap<(usize, i64, bool), Vec<(i64, bool)>>,
+
+    /// The entry addresses of subroutines currently being analyzed.
+    active_subroutines: HashSet<usize>,
+
+    /// The address of the first instruction found to underflow on a path that might not be
+    /// feasible.
+    possible_underflow: Option<usize>,
+}
+
+impl<'program> StackBalanceAnalysis<'program> {
+    fn new(program: &'program Program) -> Self {
+        Self {
+            program,
+            subroutine_exits: HashMap::new(),
+            active_subroutines: HashSet::new(),
+            possible_underflow: None,
+        }
+    }
+
+    fn check(mut self) -> std::result::Result<(), StackError> {
+        self.analyze_subroutine(0, 0, true)?;
+        match self.possible_underflow {
+            Some(address) => Err(StackError::Unknown { address }),
+            None => Ok(()),
+        }
+    }
+
+    fn analyze_subroutine(
+        &mut self,
+        entry: usize,
+        entry_height: i64,
+        is_certain: bool,
+    ) -> std::result::Result<Vec<(i64, bool)>, StackError> {
+        let key = (entry, entry_height, is_certain);
+        if let Some(exits) = self.subroutine_exits.get(&key) {
+            return Ok(exits.clone());
+        }
+        if !self.active_subroutines.insert(entry) {
+            return Err(StackError::Unknown { address: entry });
+        }
+
+        // Without `recurse`, control flow within a subroutine only ever moves forward. Hence,
+        // the exploration terminates.
+        let mut exits = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut pending = vec![(entry, entry_height, is_certain)];
+        while let Some(state) = pending.pop() {
+            if !visited.insert(state) {
+                continue;
+            }
+            let (address, height, is_certain) = state;
+            let Some(&instruction) = self.program.instructions.get(address) else {
+                continue;
+            };
+            let next_address = address + instruction.size();
+            match instruction {
+                Instruction::Halt => (),
+                Instruction::Return => _ = exits.insert((height, is_certain)),
+                Instruction::Recurse => Self::ensure_balanced_loop(address, height, entry_height)?,
+                Instruction::RecurseOrReturn => {
+                    Self::ensure_balanced_loop(address, height, entry_height)?;
+                    exits.insert((height, false));
+                }
+                Instruction::Call(target) => {
+                    let target = target.value() as usize;
+                    for exit in self.analyze_subroutine(target, height, is_certain)? {
+                        let (exit_height, returns_certainly) = exit;
+                        pending.push((next_address, exit_height, returns_certainly));
+                    }
+                }
+                Instruction::Skiz => {
+                    let Some(height) =
+                        self.height_after(instruction, address, height, is_certain)?
+                    else {
+                        continue;
+                    };
+                    pending.push((next_address, height, false));
+                    if let Some(skipped) = self.program.instructions.get(next_address) {
+                        pending.push((next_address + skipped.size(), height, false));
+                    }
+                }
+                _ => {
+                    let Some(height) =
+                        self.height_after(instruction, address, height, is_certain)?
+                    else {
+                        continue;
+                    };
+                    pending.push((next_address, height, is_certain));
+                }
+            }
+        }
+
+        self.active_subroutines.remove(&entry);
+        let exits = exits.into_iter().collect_vec();
+        self.subroutine_exits.insert(key, exits.clone());
+        Ok(exits)
+    }
+
+    /// The height of the op stack after executing the given instruction, or `None` if executing
+    /// the instruction might underflow the op stack.
+    fn height_after(
+        &mut self,
+        instruction: Instruction,
+        address: usize,
+        height: i64,
+        is_certain: bool,
+    ) -> std::result::Result<Option<i64>, StackError> {
+        let height = height + i64::from(instruction.op_stack_size_influence());
+        if height >= 0 {
+            return Ok(Some(height));
+        }
+        if is_certain {
+            return Err(StackError::Underflow { address });
+        }
+        self.possible_underflow.get_or_insert(address);
+        Ok(None)
+    }
+
+    /// Looping with a changing op stack height might eventually underflow the op stack, or leave
+    /// it at an unbounded number of heights.
+    fn ensure_balanced_loop(
+        address: usize,
+        height: i64,
+        entry_height: i64,
+    ) -> std::result::Result<(), StackError> {
+        if height != entry_height {
+            return Err(StackError::Unknown { address });
+        }
+        Ok(())
+    }
+}
+
+/// Assembles a [`Program`] instruction by instruction. Useful for generating programs from
+/// another compiler without producing [Triton assembly](crate::triton_asm) first.
+///
+/// Labels are referred to by name and may be used before they are defined. They are resolved
+/// and validated when [building](Self::build) the program.
+///
+/// # Example
+///
+/// ```
+/// # use triton_vm::prelude::*;
+/// # use triton_vm::instruction::AnInstruction;
+/// # use triton_vm::op_stack::NumberOfWords;
+/// # use triton_vm::op_stack::OpStackElement;
+/// # use triton_vm::program::ProgramBuilder;
+/// let program = ProgramBuilder::new()
+///     .push(5)
+///     .call("double")
+///     .instruction(AnInstruction::WriteIo(NumberOfWords::N1))
+///     .instruction(AnInstruction::Halt)
+///     .label("double")
+///     .instruction(AnInstruction::Dup(OpStackElement::ST0))
+///     .instruction(AnInstruction::Add)
+///     .instruction(AnInstruction::Return)
+///     .build()
+///     .unwrap();
+/// let output = program.run([].into(), [].into()).unwrap();
+/// assert_eq!(bfe_vec![10], output);
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ProgramBuilder {
+    labelled_instructions: Vec<LabelledInstruction>,
+}
+
+impl ProgramBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append any instruction. Calls refer to their target by the label's name.
+    #[must_use]
+    pub fn instruction(mut self, instruction: AnInstruction<String>) -> Self {
+        self.labelled_instructions.push(instruction.into());
+        self
+    }
+
+    /// Append instruction `push` with the given argument.
+    #[must_use]
+    pub fn push(self, element: impl Into<BFieldElement>) -> Self {
+        self.instruction(AnInstruction::Push(element.into()))
+    }
+
+    /// Append instruction `call` to the given label.
+    #[must_use]
+    pub fn call(self, label: impl Into<String>) -> Self {
+        self.instruction(AnInstruction::Call(label.into()))
+    }
+
+    /// Append instruction `push` with the address of the given label as its argument.
+    #[must_use]
+    pub fn push_label_address(mut self, label: impl Into<String>) -> Self {
+        let push = LabelledInstruction::PushLabelAddress(label.into());
+        self.labelled_instructions.push(push);
+        self
+    }
+
+    /// Mark the address of the next instruction with the given label.
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        let label = LabelledInstruction::Label(label.into());
+        self.labelled_instructions.push(label);
+        self
+    }
+
+    /// Set a [breakpoint](Program::is_breakpoint) before the next instruction.
+    #[must_use]
+    pub fn breakpoint(mut self) -> Self {
+        self.labelled_instructions
+            .push(LabelledInstruction::Breakpoint);
+        self
+    }
+
+    /// Resolve all labels and assemble the [`Program`].
+    ///
+    /// # Errors
+    ///
+    /// - if any label is defined more than once
+    /// - if any `call` or `push &<label>` refers to a label that is not defined
+    pub fn build(self) -> std::result::Result<Program, ProgramBuildingError> {
+        let mut defined_labels = HashSet::new();
+        for labelled_instruction in &self.labelled_instructions {
+            let LabelledInstruction::Label(label) = labelled_instruction else {
+                continue;
+            };
+            if !defined_labels.insert(label) {
+                return Err(ProgramBuildingError::DuplicateLabel(label.clone()));
+            }
+        }
+
+        for labelled_instruction in &self.labelled_instructions {
+            let (LabelledInstruction::Instruction(AnInstruction::Call(label))
+            | LabelledInstruction::PushLabelAddress(label)) = labelled_instruction
+            else {
+                continue;
+            };
+            if !defined_labels.contains(label) {
+                return Err(ProgramBuildingError::UndefinedLabel(label.clone()));
+            }
+        }
+
+        Ok(Program::new(&self.labelled_instructions))
+    }
+}
+
+/// A [`Program`] together with its [digest](Program::hash_with_vm_hasher), which is computed
+/// exactly once, upon construction. Equality and hashing only consider the digest, making them
+/// constant-time operations.
+///
+/// Since the program cannot be mutated through this wrapper, the digest is always up to date.
+#[derive(Debug, Clone, Eq)]
+pub struct DigestedProgram {
+    program: Program,
+    digest: Digest,
+}
+
+impl From<Program> for DigestedProgram {
+    fn from(program: Program) -> Self {
+        let digest = program.hash_with_vm_hasher();
+        Self { program, digest }
+    }
+}
+
+impl PartialEq for DigestedProgram {
+    fn eq(&self, other: &Self) -> bool {
+        self.digest == other.digest
+    }
+}
+
+impl Hash for DigestedProgram {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        Hash::hash(&self.digest, state);
+    }
+}
+
+impl DigestedProgram {
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    pub fn into_program(self) -> Program {
+        self.program
+    }
+}
+
+/// A collection of [`Program`]s without duplicates, useful for caching many programs. Programs
+/// are identified by their [digest](Program::hash_with_vm_hasher), as in [`DigestedProgram`].
+/// Every program is stored exactly once and shared by handing out [`Arc`]s.
+///
+/// Since the digest does not depend on labels, breakpoints, or type hints, inserting a program
+/// that differs from a contained program only in those aspects returns the contained program.
+#[derive(Debug, Default, Clone)]
+pub struct ProgramSet {
+    programs: HashMap<Digest, Arc<DigestedProgram>>,
+}
+
+impl ProgramSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert the given program, unless an identical program is already contained. Either way,
+    /// return the contained program.
+    ///
+    /// The program's digest is only computed if it is not already known, _i.e._, if a
+    /// [`Program`] instead of a [`DigestedProgram`] is passed.
+    pub fn insert(&mut self, program: impl Into<DigestedProgram>) -> Arc<DigestedProgram> {
+        let mut program = program.into();
+        let contained_program = self.programs.entry(program.digest).or_insert_with(|| {
+            program.program.shrink_to_fit();
+            Arc::new(program)
+        });
+
+        Arc::clone(contained_program)
+    }
+
+    /// The program with the given [digest](Program::hash_with_vm_hasher), if contained.
+    pub fn get(&self, digest: Digest) -> Option<Arc<DigestedProgram>> {
+        self.programs.get(&digest).cloned()
+    }
 
-        while !state.halting {
-            if let Err(err) = aet.record_state(&state) {
-                return Err(VMError::new(err, state));
+    pub fn contains(&self, digest: Digest) -> bool {
+        self.programs.contains_key(&digest)
+    }
+
+    pub fn len(&self) -> usize {
+        self.programs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.programs.is_empty()
+    }
+}
+
+/// Proof that a chunk of words is part of a [`Program`], given the program's
+/// [Merkle root](Program::merkle_root). See [`Program::chunk_proof`].
+///
+/// The proof only attests to the words in the chunk, not to their interpretation. In
+/// particular, the chunk's first word might not be an opcode: an instruction with an argument
+/// can straddle the chunk boundary, in which case the first word is that instruction's argument.
+/// Determining the instruction boundaries requires knowledge of all words preceding the chunk.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ProgramChunkProof {
+    pub chunk_index: usize,
+
+    /// The words of the program in the chunk, starting at address
+    /// `chunk_index·`[`MERKLE_CHUNK_LEN`](Program::MERKLE_CHUNK_LEN).
+    pub chunk: Vec<BFieldElement>,
+    pub tree_height: usize,
+    pub authentication_path: Vec<Digest>,
+}
+
+impl ProgramChunkProof {
+    /// Whether the [chunk](Self::chunk) is part of the program with the given Merkle root.
+    pub fn verify<H: AlgebraicHasher>(&self, merkle_root: Digest) -> bool {
+        let leaf = H::hash_varlen(&self.chunk);
+        let inclusion_proof = MerkleTreeInclusionProof::<H> {
+            tree_height: self.tree_height,
+            indexed_leaves: vec![(self.chunk_index, leaf)],
+            authentication_structure: self.authentication_path.clone(),
+            ..MerkleTreeInclusionProof::default()
+        };
+        inclusion_proof.verify(merkle_root)
+    }
+}
+
+/// Relates the instructions of a [`Program`] to the source code it was
+/// [parsed from](Program::from_code_with_debug_info).
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct DebugInfo {
+    address_to_location: HashMap<usize, SourceLocation>,
+    source_map: Vec<LineSpan>,
+}
+
+/// The location of an instruction in source code. See also [`DebugInfo`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SourceLocation {
+    /// The label most closely preceding the instruction, if any.
+    pub label: Option<String>,
+
+    /// The line of source code containing the instruction. The first line is line 1.
+    pub line: usize,
+}
+
+/// The lines of source code an instruction spans. See [`DebugInfo::source_map`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct LineSpan {
+    /// The first line of the instruction. The first line of source code is line 1.
+    pub first: usize,
+
+    /// The last line of the instruction, inclusive.
+    pub last: usize,
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.label {
+            Some(label) => write!(f, "at {label} (line {})", self.line),
+            None => write!(f, "at line {}", self.line),
+        }
+    }
+}
+
+impl DebugInfo {
+    fn new(code: &str, tokens: &[InstructionToken]) -> Self {
+        let line_starts = Self::line_starts(code);
+        let statement_ends = Self::statement_ends(code, tokens);
+
+        let mut address_to_location = HashMap::new();
+        let mut source_map = vec![];
+        let mut label = None;
+        let mut previous_statement = None;
+        for (token, statement_end) in tokens.iter().zip_eq(statement_ends) {
+            let instruction_size = match token {
+                InstructionToken::Label(name, _) => {
+                    label = Some(name.clone());
+                    continue;
+                }
+                InstructionToken::Instruction(instruction, _) => instruction.size(),
+                InstructionToken::PushLabelAddress(..) => Instruction::Push(bfe!(0)).size(),
+                InstructionToken::Breakpoint(_) | InstructionToken::TypeHint(..) => continue,
             };
-            let co_processor_calls = match state.step() {
-                Ok(calls) => calls,
-                Err(err) => return Err(VMError::new(err, state)),
+
+            // every token string is a suffix of the source code
+            let statement_start = code.len() - token.token_str().len();
+            let statement = statement_start..statement_end;
+            let line_span = match previous_statement {
+                Some((previous, line_span)) if previous == statement => line_span,
+                _ => Self::line_span(code, &line_starts, statement.clone()),
             };
-            for call in co_processor_calls {
-                aet.record_co_processor_call(call);
-            }
+            previous_statement = Some((statement, line_span));
+            let line = line_span.first;
+            let label = label.clone();
+            address_to_location.insert(source_map.len(), SourceLocation { label, line });
+            source_map.extend(std::iter::repeat(line_span).take(instruction_size));
         }
 
-        Ok((aet, state))
+        Self {
+            address_to_location,
+            source_map,
+        }
     }
 
-    /// Run Triton VM with the given public and secret input, recording the
-    /// influence of a callable block of instructions on the
-    /// [`AlgebraicExecutionTrace`]. For example, this can be used to identify the
-    /// number of clock cycles spent in some block of instructions, or how many rows
-    /// it contributes to the U32 Table.
-    ///
-    /// See also [`run`][run] and [`trace_execution`][trace_execution].
-    ///
-    /// [run]: Self::run
-    /// [trace_execution]: Self::trace_execution
-    pub fn profile(
-        &self,
-        public_input: PublicInput,
-        non_determinism: NonDeterminism,
-    ) -> Result<(Vec<BFieldElement>, ExecutionTraceProfile)> {
-        let mut profiler = ExecutionTraceProfiler::new(self.instructions.len());
-        let mut state = VMState::new(self, public_input, non_determinism);
-        let mut previous_jump_stack_len = state.jump_stack.len();
-        while !state.halting {
-            if let Ok(Instruction::Call(address)) = state.current_instruction() {
-                let label = self.label_for_address(address.value());
-                profiler.enter_span(label);
-            }
+    /// The byte offset of the start of every line in the given code.
+    fn line_starts(code: &str) -> Vec<usize> {
+        let line_breaks = code.match_indices('\n').map(|(offset, _)| offset + 1);
+        std::iter::once(0).chain(line_breaks).collect()
+    }
 
-            match state.step() {
-                Ok(calls) => profiler.handle_co_processor_calls(calls),
-                Err(err) => return Err(VMError::new(err, state)),
+    /// For every token, the byte offset at which the next statement starts, or the code's length
+    /// if there is no next statement. Statements such as pseudo-instructions result in multiple
+    /// tokens, all of which end at the same offset.
+    fn statement_ends(code: &str, tokens: &[InstructionToken]) -> Vec<usize> {
+        let mut statement_ends = vec![code.len(); tokens.len()];
+        for i in (1..tokens.len()).rev() {
+            let token_len = tokens[i - 1].token_str().len();
+            let next_token_len = tokens[i].token_str().len();
+            statement_ends[i - 1] = if next_token_len < token_len {
+                code.len() - next_token_len
+            } else {
+                statement_ends[i]
             };
-
-            if state.jump_stack.len() < previous_jump_stack_len {
-                profiler.exit_span();
-            }
-            previous_jump_stack_len = state.jump_stack.len();
         }
 
-        Ok((state.public_output, profiler.finish()))
+        statement_ends
     }
 
-    /// The label for the given address, or a deterministic, unique substitute if no label is found.
-    pub fn label_for_address(&self, address: u64) -> String {
-        // Uniqueness of the label is relevant for printing and subsequent parsing:
-        // Parsing fails on duplicate labels.
-        self.address_to_label
-            .get(&address)
-            .cloned()
-            .unwrap_or_else(|| format!("address_{address}"))
+    /// The lines spanned by the statement at the given byte range. The statement is followed by
+    /// whitespace and comments up to the next statement, which are not part of the span.
+    fn line_span(code: &str, line_starts: &[usize], statement: Range<usize>) -> LineSpan {
+        let is_code = |line: &str| {
+            !line
+                .split("//")
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .is_empty()
+        };
+        let num_additional_lines = code[statement.clone()]
+            .lines()
+            .positions(is_code)
+            .last()
+            .unwrap_or_default();
+
+        let first = line_starts.partition_point(|&line_start| line_start <= statement.start);
+        let last = first + num_additional_lines;
+        LineSpan { first, last }
+    }
+
+    /// The source code location of the instruction at the given address, if known.
+    pub fn source_location(&self, address: usize) -> Option<&SourceLocation> {
+        self.address_to_location.get(&address)
+    }
+
+    /// The [lines](LineSpan) of source code for every address of the program. The element at
+    /// index `i` belongs to address `i`. An instruction taking an argument occupies two
+    /// addresses, both of which map to that instruction's lines. Useful for relating profiles
+    /// or coverage information to source code.
+    pub fn source_map(&self) -> &[LineSpan] {
+        &self.source_map
     }
 }
 
+/// The [control-flow graph](https://en.wikipedia.org/wiki/Control-flow_graph) of a [`Program`].
+/// See [`Program::control_flow_graph`].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ControlFlowGraph {
+    /// The basic blocks, ordered by address.
+    pub blocks: Vec<BasicBlock>,
+
+    /// The edges between the [basic blocks](Self::blocks), grouped by source block.
+    pub edges: Vec<ControlFlowEdge>,
+}
+
+/// A maximal sequence of instructions that is always executed from start to end. Part of a
+/// [`ControlFlowGraph`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BasicBlock {
+    /// The address of the block's first instruction.
+    pub start: usize,
+
+    /// The address immediately following the block's last instruction.
+    pub end: usize,
+
+    /// The label of the block's first instruction, if any.
+    pub label: Option<String>,
+}
+
+/// A possible transfer of control from the end of one [`BasicBlock`] to the start of another.
+/// Blocks are identified by the address of their first instruction.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ControlFlowEdge {
+    pub source: usize,
+    pub target: usize,
+    pub kind: ControlFlowEdgeKind,
+}
+
+/// The reason for a [`ControlFlowEdge`].
+///
+/// Instructions `return`, `recurse`, and `recurse_or_return` have no outgoing edges since their
+/// destination depends on the jump stack. Instead, the `return` from a `call` is represented by
+/// an edge from the `call` to the instruction following it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ControlFlowEdgeKind {
+    /// Execution continues with the next instruction.
+    FallThrough,
+
+    /// Instruction `call` jumps to its target.
+    Call,
+
+    /// The subroutine invoked by instruction `call` returns to the instruction following it.
+    Return,
+
+    /// Instruction `skiz` found a non-zero element and continues with the next instruction.
+    NonZero,
+
+    /// Instruction `skiz` found zero and skips the next instruction.
+    Zero,
+}
+
+/// A single row in a [program listing](Program::listing), relating an instruction to its
+/// address and its encoding.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ListingRow {
+    /// The address of the instruction, _i.e._, the value of the
+    /// [instruction pointer](VMState::instruction_pointer) when executing it.
+    pub address: usize,
+
+    /// The instruction as it would appear in source code. The address of instruction
+    /// [`call`](Instruction::Call) is replaced by the corresponding label.
+    pub instruction: AnInstruction<String>,
+
+    /// The [`BFieldElement`]s the instruction is encoded as: the opcode, followed by the
+    /// argument if the instruction has one.
+    pub words: Vec<BFieldElement>,
+}
+
 #[derive(Debug, Default, Clone, Eq, PartialEq, Arbitrary)]
 struct ExecutionTraceProfiler {
     call_stack: Vec<usize>,
@@ -839,6 +2674,33 @@ impl PublicInput {
     }
 }
 
+/// A source of public input that is consumed lazily, _i.e._, only once the program reads from it
+/// using instruction `read_io`. See, for example,
+/// [`Program::run_with_input_source`].
+///
+/// Any closure returning `Option<BFieldElement>` is an `InputSource`. This makes it easy to
+/// stream input from an iterator: `move || iterator.next()`.
+pub trait InputSource {
+    /// The next element of public input, or `None` if the input is exhausted. May block.
+    fn next(&mut self) -> Option<BFieldElement>;
+}
+
+impl InputSource for VecDeque<BFieldElement> {
+    fn next(&mut self) -> Option<BFieldElement> {
+        self.pop_front()
+    }
+}
+
+impl<F: FnMut() -> Option<BFieldElement>> InputSource for F {
+    fn next(&mut self) -> Option<BFieldElement> {
+        self()
+    }
+}
+
+fn no_input() -> VecDeque<BFieldElement> {
+    VecDeque::new()
+}
+
 /// All sources of non-determinism for a program. This includes elements that
 /// can be read using instruction `divine`, digests that can be read using
 /// instruction `merkle_step`, and an initial state of random-access memory.
@@ -874,6 +2736,12 @@ impl From<&[BFieldElement]> for NonDeterminism {
 }
 
 impl NonDeterminism {
+    /// The number of individual tokens generated by [`from_seed`](Self::from_seed).
+    pub const NUM_SEEDED_INDIVIDUAL_TOKENS: usize = 1 << 10;
+
+    /// The number of digests generated by [`from_seed`](Self::from_seed).
+    pub const NUM_SEEDED_DIGESTS: usize = 1 << 6;
+
     pub fn new<V: Into<Vec<BFieldElement>>>(individual_tokens: V) -> Self {
         Self {
             individual_tokens: individual_tokens.into(),
@@ -882,12 +2750,54 @@ impl NonDeterminism {
         }
     }
 
+    /// Pseudo-random individual tokens and digests, deterministically derived from the given
+    /// seed. Makes runs that depend on non-determinism reproducible from a single integer, for
+    /// example in bug reports. The initial RAM is empty.
+    ///
+    /// Exactly [`NUM_SEEDED_INDIVIDUAL_TOKENS`](Self::NUM_SEEDED_INDIVIDUAL_TOKENS) individual
+    /// tokens and [`NUM_SEEDED_DIGESTS`](Self::NUM_SEEDED_DIGESTS) digests are generated. A
+    /// program reading more than that crashes with
+    /// [`InputExhausted`](InstructionError::InputExhausted) or
+    /// [`EmptySecretDigestInput`](InstructionError::EmptySecretDigestInput), respectively. For
+    /// such programs, generate the required amount of non-determinism with a seeded random
+    /// number generator directly.
+    ///
+    /// The generated values are stable for a given version of Triton VM, but might change across
+    /// versions.
+    pub fn from_seed(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let individual_tokens = (0..Self::NUM_SEEDED_INDIVIDUAL_TOKENS)
+            .map(|_| rng.gen())
+            .collect_vec();
+        let digests = (0..Self::NUM_SEEDED_DIGESTS)
+            .map(|_| rng.gen())
+            .collect_vec();
+
+        Self::new(individual_tokens).with_digests(digests)
+    }
+
     #[must_use]
     pub fn with_digests<V: Into<Vec<Digest>>>(mut self, digests: V) -> Self {
         self.digests = digests.into();
         self
     }
 
+    /// Append the given Merkle authentication paths to the [digests](Self::digests), in the
+    /// order in which instruction `merkle_step` consumes them. Each path lists the siblings from
+    /// the leaf level up to, but excluding, the root.
+    ///
+    /// ```
+    /// # use triton_vm::prelude::*;
+    /// # let [a, b, c] = [Digest::default(); 3];
+    /// let non_determinism = NonDeterminism::default().with_merkle_paths(vec![vec![a, b], vec![c]]);
+    /// assert_eq!(vec![a, b, c], non_determinism.digests);
+    /// ```
+    #[must_use]
+    pub fn with_merkle_paths<V: Into<Vec<Vec<Digest>>>>(mut self, paths: V) -> Self {
+        self.digests.extend(paths.into().into_iter().flatten());
+        self
+    }
+
     #[must_use]
     pub fn with_ram<H: Into<HashMap<BFieldElement, BFieldElement>>>(mut self, ram: H) -> Self {
         self.ram = ram.into();
@@ -897,17 +2807,23 @@ impl NonDeterminism {
 
 #[cfg(test)]
 mod tests {
+    use std::convert::Infallible;
+    use std::io;
+
     use assert2::assert;
     use assert2::let_assert;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
+    use rand::rngs::StdRng;
     use rand::thread_rng;
     use rand::Rng;
+    use rand::SeedableRng;
     use test_strategy::proptest;
     use twenty_first::prelude::Tip5;
 
-    use crate::error::InstructionError;
+    use crate::error::InputKind;
     use crate::example_programs::CALCULATE_NEW_MMR_PEAKS_FROM_APPEND_WITH_SAFE_LISTS;
+    use crate::example_programs::FIBONACCI_SEQUENCE;
     use crate::table::master_table::TableId;
     use crate::triton_program;
 
@@ -972,13 +2888,448 @@ mod tests {
         ];
         let expected_digest = Digest::new(expected_digest);
 
-        assert!(expected_digest == digest);
+        assert!(expected_digest == digest);
+    }
+
+    #[test]
+    fn programs_ending_in_halt_are_recognized() {
+        assert!(triton_program!(halt).ends_in_halt());
+        assert!(triton_program!(push 1 pop 1 halt).ends_in_halt());
+        assert!(triton_program!(call foo halt foo: push 1 pop 1 return).ends_in_halt());
+        assert!(triton_program!(call main main: push 0 pop 1 halt).ends_in_halt());
+        assert!(triton_program!(push 0 skiz nop halt).ends_in_halt());
+        assert!(triton_program!(call foo foo: call bar bar: halt).ends_in_halt());
+        assert!(triton_program!(call foo call foo halt foo: return).ends_in_halt());
+        assert!(let Ok(()) = triton_program!(halt).validate());
+    }
+
+    #[test]
+    fn programs_not_ending_in_halt_are_recognized() {
+        assert!(!triton_program!().ends_in_halt());
+        assert!(!triton_program!(push 1 pop 1).ends_in_halt());
+        assert!(!triton_program!(push 0 skiz halt).ends_in_halt());
+        assert!(!triton_program!(return halt).ends_in_halt());
+        assert!(!triton_program!(call foo foo: return).ends_in_halt());
+        assert!(!triton_program!(call foo foo: call foo return).ends_in_halt());
+        assert!(!triton_program!(call foo halt foo: recurse).ends_in_halt());
+        assert!(!triton_program!(push 1 skiz call foo halt foo: return).ends_in_halt());
+
+        let_assert!(Err(err) = triton_program!(push 1).validate());
+        assert!(ProgramValidationError::MissingHalt == err);
+    }
+
+    #[test]
+    fn conditionally_recursing_program_is_not_certain_to_halt() {
+        let program = triton_program!(call foo halt foo: push 1 skiz recurse halt);
+        assert!(!program.ends_in_halt());
+        let_assert!(Err(ProgramValidationError::MissingHalt) = program.validate());
+    }
+
+    #[test]
+    fn hashing_with_vm_hasher_agrees_with_program_digest_on_op_stack() {
+        let program = CALCULATE_NEW_MMR_PEAKS_FROM_APPEND_WITH_SAFE_LISTS.clone();
+        let digest = program.hash_with_vm_hasher();
+        assert!(program.hash::<Tip5>() == digest);
+
+        let vm_state = VMState::new(&program, [].into(), [].into());
+        let mut op_stack_bottom = vm_state.op_stack.stack[..tip5::DIGEST_LENGTH].to_vec();
+        op_stack_bottom.reverse();
+        assert!(digest.values().to_vec() == op_stack_bottom);
+    }
+
+    #[test]
+    fn random_programs_terminate_without_panicking() {
+        let mut rng = StdRng::seed_from_u64(315);
+        for _ in 0..1000 {
+            let approx_len = rng.gen_range(0..100);
+            let program = Program::random(&mut rng, approx_len);
+            let public_input = (0..rng.gen_range(0..10)).map(|_| bfe!(rng.gen::<u32>()));
+            let public_input = PublicInput::new(public_input.collect());
+            let _ = program.run(public_input, [].into());
+        }
+    }
+
+    #[test]
+    fn empty_program_is_empty() {
+        let program = triton_program!();
+        assert!(program.is_empty());
+    }
+
+    #[test]
+    fn constants_of_program_with_repeated_pushes() {
+        let program = triton_program! {
+            push 7 push 3 dup 0 push 7 call foo
+            push -1 halt
+            foo: push 3 push 7 pop 3 return
+        };
+        let expected_constants = bfe_vec![7, 3, 7, -1, 3, 7];
+        assert!(expected_constants == program.constants());
+
+        let histogram = program.constant_histogram();
+        assert!(3 == histogram.len());
+        assert!(3 == histogram[&bfe!(7)]);
+        assert!(2 == histogram[&bfe!(3)]);
+        assert!(1 == histogram[&bfe!(-1)]);
+    }
+
+    #[test]
+    fn annotated_listing_of_program_with_loop() {
+        let program = triton_program! {
+            push 3 call countdown halt
+            countdown:
+                dup 0 push 0 eq skiz return
+                push -1 add recurse
+        };
+        let expected_listing = [
+            "    0  push 3",
+            "    2  call countdown",
+            "    4  halt",
+            "countdown:",
+            "    5  dup 0",
+            "    7  push 0",
+            "    9  eq",
+            "   10  skiz",
+            "   11  return",
+            "   12  push -1",
+            "   14  add",
+            "   15  recurse",
+            "",
+        ]
+        .join("\n");
+        assert!(expected_listing == program.to_string_annotated());
+    }
+
+    #[test]
+    fn listing_relates_instructions_to_addresses_and_encoding() {
+        let program = triton_program!(nop push 5 call foo halt foo: return);
+        let listing = program.listing();
+        assert!(5 == listing.len());
+
+        let push_row = &listing[1];
+        assert!(1 == push_row.address);
+        assert!(AnInstruction::Push(bfe!(5)) == push_row.instruction);
+        assert!(vec![Instruction::Push(bfe!(0)).opcode_b(), bfe!(5)] == push_row.words);
+
+        let call_row = &listing[2];
+        assert!(3 == call_row.address);
+        assert!(AnInstruction::Call("foo".to_string()) == call_row.instruction);
+        assert!(bfe!(6) == call_row.words[1]);
+
+        let return_row = &listing[4];
+        assert!(6 == return_row.address);
+        assert!(vec![Instruction::Return.opcode_b()] == return_row.words);
+    }
+
+    #[test]
+    fn dot_graph_contains_basic_blocks_and_control_flow() {
+        let program = triton_program!(
+            push 1 skiz call foo halt
+            foo: push 2 pop 1 return
+        );
+        let dot = program.to_dot();
+
+        assert!(dot.starts_with("digraph program {"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert!(dot.contains("ip_0 [label=\"address_0:\\l0: push 1\\l2: skiz\\l\"];"));
+        assert!(dot.contains("ip_3 [label=\"address_3:\\l3: call foo\\l\"];"));
+        assert!(dot.contains("ip_5 [label=\"address_5:\\l5: halt\\l\"];"));
+        assert!(dot.contains("ip_6 [label=\"foo:\\l"));
+
+        assert!(dot.contains("ip_0 -> ip_3 [label=\"nonzero\"];"));
+        assert!(dot.contains("ip_0 -> ip_5 [label=\"zero\"];"));
+        assert!(dot.contains("ip_3 -> ip_6 [label=\"call\"];"));
+        assert!(dot.contains("ip_3 -> ip_5 [label=\"return\", style=dashed];"));
+        assert!(4 == dot.matches("->").count());
+    }
+
+    #[test]
+    fn control_flow_graph_of_program_with_loop_and_conditional() {
+        let program = triton_program!(
+            push 3 call loop halt
+            loop:
+                dup 0 push 0 eq skiz return
+                addi -1 recurse
+        );
+        let control_flow_graph = program.control_flow_graph();
+
+        let block = |start, end, label: Option<&str>| BasicBlock {
+            start,
+            end,
+            label: label.map(|l| l.to_string()),
+        };
+        let expected_blocks = vec![
+            block(0, 4, None),
+            block(4, 5, None),
+            block(5, 11, Some("loop")),
+            block(11, 12, None),
+            block(12, 15, None),
+        ];
+        assert!(expected_blocks == control_flow_graph.blocks);
+
+        let edge = |source, target, kind| ControlFlowEdge {
+            source,
+            target,
+            kind,
+        };
+        let expected_edges = vec![
+            edge(0, 5, ControlFlowEdgeKind::Call),
+            edge(0, 4, ControlFlowEdgeKind::Return),
+            edge(5, 11, ControlFlowEdgeKind::NonZero),
+            edge(5, 12, ControlFlowEdgeKind::Zero),
+        ];
+        assert!(expected_edges == control_flow_graph.edges);
+    }
+
+    #[proptest]
+    fn basic_blocks_partition_the_program(#[strategy(arb())] program: Program) {
+        let blocks = program.control_flow_graph().blocks;
+        let mut address = 0;
+        for block in blocks {
+            prop_assert_eq!(address, block.start);
+            prop_assert!(block.start < block.end);
+            address = block.end;
+        }
+        prop_assert_eq!(program.len_bwords(), address);
+    }
+
+    #[test]
+    fn labels_split_basic_blocks() {
+        let program = triton_program!(push 1 bar: pop 1 halt);
+        let dot = program.to_dot();
+        assert!(dot.contains("ip_2 [label=\"bar:\\l2: pop 1\\l3: halt\\l\"];"));
+        assert!(dot.contains("ip_0 -> ip_2 [];"));
+    }
+
+    #[proptest]
+    fn dot_graph_of_random_program_has_node_for_entry_point(#[strategy(arb())] program: Program) {
+        let dot = program.to_dot();
+        if !program.is_empty() {
+            prop_assert!(dot.contains("ip_0 [label="));
+        }
+    }
+
+    #[proptest]
+    fn concatenated_words_of_listing_are_program_encoding(#[strategy(arb())] program: Program) {
+        let words = program.listing().into_iter().flat_map(|row| row.words);
+        prop_assert_eq!(program.to_bwords(), words.collect_vec());
+    }
+
+    #[test]
+    fn iterating_over_program_skips_instruction_arguments() {
+        let program = triton_program!(push 1 nop dup 0 halt);
+        let expected = vec![
+            Instruction::Push(bfe!(1)),
+            Instruction::Nop,
+            Instruction::Dup(OpStackElement::ST0),
+            Instruction::Halt,
+        ];
+        assert!(expected == program.into_iter().collect_vec());
+    }
+
+    #[test]
+    fn clean_code_results_in_no_warnings() {
+        let code = "push -1 call foo halt\nfoo:\n  push 2 skiz return\n  recurse";
+        let (program, warnings) = Program::from_code_checked(code).unwrap();
+        assert!(Program::from_code(code).unwrap() == program);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn spelled_out_minus_one_results_in_warning() {
+        let code = "push 1 18446744069414584320 // the constant\nhalt";
+        let (_, warnings) = Program::from_code_checked(code).unwrap();
+        let_assert!([Warning::SpelledOutMinusOne { span }] = warnings.as_slice());
+        assert!("push 1 18446744069414584320" == &code[span.clone()]);
+    }
+
+    #[test]
+    fn code_after_halt_results_in_warning() {
+        let code = "halt\npush 1 pop 1\nfoo: return";
+        let (_, warnings) = Program::from_code_checked(code).unwrap();
+        let_assert!([Warning::DeadCodeAfterHalt { span }] = warnings.as_slice());
+        assert!("push 1 pop 1" == &code[span.clone()]);
+    }
+
+    #[test]
+    fn code_after_unconditional_return_results_in_warning() {
+        let code = "call foo halt foo: return nop";
+        let (_, warnings) = Program::from_code_checked(code).unwrap();
+        let_assert!([Warning::UnreachableCode { span }] = warnings.as_slice());
+        assert!("nop" == &code[span.clone()]);
+    }
+
+    #[test]
+    fn subroutine_without_return_results_in_warning() {
+        let code = "call foo halt\nfoo: push 1 pop 1 recurse";
+        let (_, warnings) = Program::from_code_checked(code).unwrap();
+        let_assert!([Warning::MissingReturn { label, span }] = warnings.as_slice());
+        assert!("foo" == label);
+        assert!("foo:" == &code[span.clone()]);
+    }
+
+    #[test]
+    fn recurse_without_progress_results_in_warning() {
+        let code = "call foo halt\nfoo: dup 0 push 0 eq skiz return\n  swap 1 swap 1\n  recurse";
+        let (_, warnings) = Program::from_code_checked(code).unwrap();
+        let_assert!([Warning::RecurseWithoutProgress { label, span }] = warnings.as_slice());
+        assert!("foo" == label);
+        assert!("recurse" == &code[span.clone()]);
+    }
+
+    #[test]
+    fn recurse_with_progress_results_in_no_warning() {
+        let code = "call foo halt\nfoo: dup 0 push 0 eq skiz return\n  push -1 add\n  recurse";
+        let (_, warnings) = Program::from_code_checked(code).unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn debug_info_maps_addresses_to_source_lines_and_labels() {
+        let code = "push 1\ncall foo\nhalt\n\nfoo:\n  push 2 3\n  return\n";
+        let (program, debug_info) = Program::from_code_with_debug_info(code).unwrap();
+        assert!(Program::from_code(code).unwrap() == program);
+
+        let location = |address| debug_info.source_location(address).cloned();
+        let_assert!(Some(first) = location(0));
+        assert!(None == first.label);
+        assert!(1 == first.line);
+        assert!("at line 1" == first.to_string());
+
+        let_assert!(Some(halt) = location(4));
+        assert!(3 == halt.line);
+
+        let_assert!(Some(push_3) = location(7));
+        assert!(Some("foo".to_string()) == push_3.label);
+        assert!(6 == push_3.line);
+        assert!("at foo (line 6)" == push_3.to_string());
+
+        assert!(None == location(1));
+        assert!(None == location(11));
+    }
+
+    #[test]
+    fn source_map_maps_every_address_to_lines_of_its_instruction() {
+        let code = "push 1 // one\ncall foo\nhalt\n\nfoo:\n  push\n    2\n  pop 1 return\n";
+        let (program, debug_info) = Program::from_code_with_debug_info(code).unwrap();
+        let source_map = debug_info.source_map();
+        assert!(program.len_bwords() == source_map.len());
+
+        let lines = |first, last| LineSpan { first, last };
+        assert!(lines(1, 1) == source_map[0]);
+        assert!(lines(1, 1) == source_map[1]);
+        assert!(lines(2, 2) == source_map[2]);
+        assert!(lines(3, 3) == source_map[4]);
+        assert!(lines(6, 7) == source_map[5]);
+        assert!(lines(6, 7) == source_map[6]);
+        assert!(lines(8, 8) == source_map[7]);
+        assert!(lines(8, 8) == source_map[9]);
+    }
+
+    #[test]
+    fn source_map_of_pseudo_instruction_spans_whole_statement() {
+        let code = "push 1 2 3\nhalt";
+        let (_, debug_info) = Program::from_code_with_debug_info(code).unwrap();
+        let source_map = debug_info.source_map();
+        assert!(source_map[..6]
+            .iter()
+            .all(|&span| LineSpan { first: 1, last: 1 } == span));
+        assert!(LineSpan { first: 2, last: 2 } == source_map[6]);
+    }
+
+    #[proptest]
+    fn cached_digest_is_program_digest(#[strategy(arb())] program: Program) {
+        let digested_program = program.clone().with_cached_digest();
+        prop_assert_eq!(program.hash_with_vm_hasher(), digested_program.digest());
+        prop_assert_eq!(&program, digested_program.program());
+    }
+
+    #[test]
+    fn digested_programs_are_equal_iff_programs_are_equal() {
+        let program = triton_program!(push 1 push 2 add halt);
+        let same_program = triton_program!(push 1 push 2 add halt);
+        let other_program = triton_program!(push 1 push 3 add halt);
+
+        let digested_program = program.with_cached_digest();
+        assert!(digested_program == same_program.with_cached_digest());
+        assert!(digested_program != other_program.with_cached_digest());
+
+        let cache = HashSet::from([digested_program.clone()]);
+        assert!(cache.contains(&digested_program));
+    }
+
+    #[test]
+    fn program_set_shares_identical_programs() {
+        let program = triton_program!(push 1 push 2 add halt);
+        let other_program = triton_program!(push 1 push 3 add halt);
+
+        let mut program_set = ProgramSet::new();
+        let first = program_set.insert(program.clone());
+        let second = program_set.insert(program.clone());
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(1 == program_set.len());
+
+        let other = program_set.insert(other_program);
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert!(2 == program_set.len());
+
+        let_assert!(Some(contained) = program_set.get(program.hash_with_vm_hasher()));
+        assert!(Arc::ptr_eq(&first, &contained));
+
+        let digested_program = program.with_cached_digest();
+        let third = program_set.insert(digested_program);
+        assert!(Arc::ptr_eq(&first, &third));
+        assert!(2 == program_set.len());
+    }
+
+    #[test]
+    fn shrinking_program_to_fit_releases_excess_capacity() {
+        let program = triton_program!(push 1 foo: push 2 add break call foo halt);
+        let mut shrunk_program = program.clone();
+        shrunk_program.instructions.reserve(1000);
+        shrunk_program.breakpoints.reserve(1000);
+
+        shrunk_program.shrink_to_fit();
+        let instructions = &shrunk_program.instructions;
+        assert!(instructions.len() == instructions.capacity());
+        let breakpoints = &shrunk_program.breakpoints;
+        assert!(breakpoints.len() == breakpoints.capacity());
+
+        assert!(program == shrunk_program);
+        assert!(program.labelled_instructions() == shrunk_program.labelled_instructions());
+    }
+
+    #[test]
+    fn program_reading_only_public_input_is_deterministic() {
+        let program = triton_program!(read_io 2 add push 5 write_mem 1 pop 1 write_io 1 halt);
+        assert!(!program.reads_secret_input());
+        assert!(program.is_deterministic_in_public_input());
+    }
+
+    #[test]
+    fn program_divining_is_not_deterministic_in_public_input() {
+        let program = triton_program!(read_io 1 divine 1 add write_io 1 halt);
+        assert!(program.reads_secret_input());
+        assert!(!program.is_deterministic_in_public_input());
     }
 
     #[test]
-    fn empty_program_is_empty() {
-        let program = triton_program!();
-        assert!(program.is_empty());
+    fn unreachable_merkle_step_is_considered_reading_secret_input() {
+        let program = triton_program!(halt merkle_step halt);
+        assert!(program.reads_secret_input());
+    }
+
+    #[test]
+    fn program_reading_ram_is_not_deterministic_in_public_input() {
+        let program = triton_program!(push 42 read_mem 1 pop 1 write_io 1 halt);
+        assert!(!program.reads_secret_input());
+        assert!(!program.is_deterministic_in_public_input());
+    }
+
+    #[test]
+    fn program_without_pushes_has_no_constants() {
+        let program = triton_program!(dup 0 pop 1 call foo halt foo: return);
+        assert!(program.constants().is_empty());
+        assert!(program.constant_histogram().is_empty());
     }
 
     #[proptest]
@@ -1067,6 +3418,728 @@ mod tests {
         println!("{profile}");
     }
 
+    #[test]
+    fn bounded_trace_execution_agrees_with_unbounded_trace_execution_if_within_bounds() {
+        let program = CALCULATE_NEW_MMR_PEAKS_FROM_APPEND_WITH_SAFE_LISTS.clone();
+        let (aet, output) = program.trace_execution([].into(), [].into()).unwrap();
+        let num_cycles = aet.processor_trace.nrows().try_into().unwrap();
+
+        let_assert!(
+            Ok((bounded_aet, bounded_output)) =
+                program.trace_execution_bounded([].into(), [].into(), num_cycles)
+        );
+        assert!(output == bounded_output);
+        assert!(aet.processor_trace == bounded_aet.processor_trace);
+    }
+
+    #[test]
+    fn stack_balance_check_accepts_balanced_programs() {
+        let program = triton_program!(push 1 push 2 add write_io 1 halt);
+        assert!(let Ok(()) = program.check_stack_balance());
+
+        let program = triton_program! {
+            push 3 call countdown pop 1 halt
+            countdown:
+                dup 0 write_io 1
+                push -1 add
+                dup 0 push 0 eq skiz return
+                recurse
+        };
+        assert!(let Ok(()) = program.check_stack_balance());
+    }
+
+    #[test]
+    fn stack_balance_check_detects_certain_underflow() {
+        let program = triton_program!(push 1 pop 2 halt);
+        let_assert!(Err(err) = program.check_stack_balance());
+        assert!(StackError::Underflow { address: 2 } == err);
+
+        let program = triton_program!(push 1 call consume_two halt consume_two: pop 2 return);
+        let_assert!(Err(err) = program.check_stack_balance());
+        assert!(StackError::Underflow { address: 5 } == err);
+
+        let_assert!(Err(err) = program.run([].into(), [].into()));
+        assert!(InstructionError::OpStackTooShallow == err.source);
+        assert!(5 == err.vm_state.instruction_pointer);
+    }
+
+    #[test]
+    fn stack_balance_check_is_inconclusive_for_underflow_in_branch() {
+        let program = triton_program!(read_io 1 skiz pop 1 halt);
+        let_assert!(Err(err) = program.check_stack_balance());
+        assert!(StackError::Unknown { address: 3 } == err);
+    }
+
+    #[test]
+    fn stack_balance_check_is_inconclusive_for_loops_changing_the_stack_height() {
+        let program = triton_program!(call grow halt grow: push 1 recurse);
+        let_assert!(Err(err) = program.check_stack_balance());
+        assert!(StackError::Unknown { address: 5 } == err);
+    }
+
+    fn program_with_cosmetic_noise() -> Program {
+        triton_program! {
+            read_io 1 nop nop
+            call double
+            nop
+            write_io 1
+            halt
+            push 5 nop pop 1
+            double:
+                dup 0 nop skiz nop
+                push 2 nop mul
+                dup 0 push 0 eq skiz halt
+                nop nop
+                return
+        }
+    }
+
+    #[test]
+    fn canonicalization_removes_cosmetic_differences() {
+        let program = program_with_cosmetic_noise();
+        let expected = triton_program! {
+            read_io 1
+            call label_0
+            write_io 1
+            halt
+            label_0:
+                dup 0 skiz nop
+                push 2 mul
+                dup 0 push 0 eq skiz halt
+                return
+        };
+        let canonical_program = program.canonicalize();
+        assert!(expected == canonical_program);
+        assert!("label_0" == canonical_program.label_for_address(7));
+        assert!(canonical_program == canonical_program.canonicalize());
+
+        let output = program.run(bfe_vec![0].into(), [].into()).unwrap();
+        let canonical_output = canonical_program.run(bfe_vec![0].into(), [].into());
+        assert!(output == canonical_output.unwrap());
+    }
+
+    #[proptest]
+    fn canonicalization_preserves_output(#[strategy(arb())] input: BFieldElement) {
+        let program = program_with_cosmetic_noise();
+        let output = program.run(bfe_vec![input].into(), [].into()).unwrap();
+        let canonical_output = program
+            .canonicalize()
+            .run(bfe_vec![input].into(), [].into())
+            .unwrap();
+        prop_assert_eq!(output, canonical_output);
+    }
+
+    #[test]
+    fn ram_log_contains_every_write_in_order() {
+        let program = triton_program!(
+            push 1 push 5 write_mem 1 pop 1
+            push 5 read_mem 1 pop 2
+            push 2 push 5 write_mem 1 pop 1
+            halt
+        );
+        let non_determinism = NonDeterminism::default().with_ram([(bfe!(5), bfe!(9))]);
+        let (_, ram_log) = program
+            .run_with_ram_log([].into(), non_determinism)
+            .unwrap();
+
+        let first_write = RamWrite {
+            cycle: 2,
+            address: bfe!(5),
+            old_value: bfe!(9),
+            new_value: bfe!(1),
+        };
+        let second_write = RamWrite {
+            cycle: 9,
+            address: bfe!(5),
+            old_value: bfe!(1),
+            new_value: bfe!(2),
+        };
+        assert!(vec![first_write, second_write] == ram_log);
+    }
+
+    #[test]
+    fn non_determinism_from_same_seed_is_identical() {
+        assert!(NonDeterminism::from_seed(42) == NonDeterminism::from_seed(42));
+        assert!(NonDeterminism::from_seed(42) != NonDeterminism::from_seed(43));
+
+        let program = triton_program!(divine 5 merkle_step write_io 5 halt);
+        let output = |seed| program.run([].into(), NonDeterminism::from_seed(seed));
+        assert!(output(42).unwrap() == output(42).unwrap());
+    }
+
+    #[test]
+    fn program_accepts_merkle_paths_supplied_as_non_determinism() {
+        let tree_height = 3;
+        let num_leaves = 1 << tree_height;
+        let leaves = (0..num_leaves)
+            .map(|i| Tip5::hash_varlen(&bfe_vec![i]))
+            .collect_vec();
+        let tree: MerkleTree<Tip5> = CpuParallel::from_digests(&leaves).unwrap();
+
+        let push_digest = |Digest([d0, d1, d2, d3, d4]): Digest| triton_asm!(push {d4} push {d3} push {d2} push {d1} push {d0});
+        let verify_leaf = |leaf_index: usize| {
+            let node_index = num_leaves + leaf_index;
+            triton_asm!(
+                push {node_index}
+                {&push_digest(leaves[leaf_index])}
+                {&triton_asm![merkle_step; tree_height]}
+                {&push_digest(tree.root())}
+                assert_vector pop 5 pop 5
+                assert
+            )
+        };
+        let leaf_indices = [5, 2];
+        let program = triton_program!(
+            {&verify_leaf(leaf_indices[0])}
+            {&verify_leaf(leaf_indices[1])}
+            halt
+        );
+
+        let paths = leaf_indices
+            .iter()
+            .map(|&i| tree.authentication_structure(&[i]).unwrap())
+            .collect_vec();
+        let non_determinism = NonDeterminism::default().with_merkle_paths(paths);
+        let_assert!(Ok(_) = program.run([].into(), non_determinism));
+    }
+
+    #[proptest]
+    fn chunk_proofs_verify_against_merkle_root(#[strategy(arb())] program: Program) {
+        let merkle_root = program.merkle_root::<Tip5>();
+        let num_chunks = program.len_bwords().div_ceil(Program::MERKLE_CHUNK_LEN);
+        for chunk_index in 0..num_chunks {
+            let proof = program.chunk_proof::<Tip5>(chunk_index).unwrap();
+            prop_assert!(proof.verify::<Tip5>(merkle_root));
+        }
+        prop_assert!(program.chunk_proof::<Tip5>(num_chunks).is_none());
+    }
+
+    #[test]
+    fn chunk_proof_discloses_program_words_at_chunk_address() {
+        let program = FIBONACCI_SEQUENCE.clone();
+        let proof = program.chunk_proof::<Tip5>(1).unwrap();
+        let start = Program::MERKLE_CHUNK_LEN;
+        let end = start + proof.chunk.len();
+        assert!(program.to_bwords()[start..end] == proof.chunk);
+    }
+
+    #[test]
+    fn tampered_chunk_proof_is_rejected() {
+        let program = FIBONACCI_SEQUENCE.clone();
+        let merkle_root = program.merkle_root::<Tip5>();
+        let mut proof = program.chunk_proof::<Tip5>(1).unwrap();
+        assert!(proof.verify::<Tip5>(merkle_root));
+
+        proof.chunk[0] += bfe!(1);
+        assert!(!proof.verify::<Tip5>(merkle_root));
+    }
+
+    #[proptest]
+    fn streaming_hash_agrees_with_hash(#[strategy(arb())] program: Program) {
+        prop_assert_eq!(program.hash::<Tip5>(), program.hash_streaming::<Tip5>());
+    }
+
+    #[test]
+    fn streaming_hash_agrees_with_hash_for_programs_of_various_lengths() {
+        for num_nops in 0..=2 * tip5::RATE {
+            let program = triton_program!({&triton_asm![nop; num_nops]} push 42 halt);
+            assert!(program.hash::<Tip5>() == program.hash_streaming::<Tip5>());
+        }
+
+        let program = CALCULATE_NEW_MMR_PEAKS_FROM_APPEND_WITH_SAFE_LISTS.clone();
+        assert!(program.hash::<Tip5>() == program.hash_streaming::<Tip5>());
+    }
+
+    #[test]
+    fn find_all_pairs_of_swap_1() {
+        let program = triton_program!(swap 1 swap 1 push 1 swap 1 swap 1 swap 1 swap 2 halt);
+        let swap_1 = InstructionMatcher::Exact(Instruction::Swap(OpStackElement::ST1));
+        assert!(vec![0, 6, 8] == program.find_pattern(&[swap_1, swap_1]));
+    }
+
+    #[test]
+    fn pattern_search_respects_instruction_boundaries() {
+        let program = triton_program!(push 1 push 1 push 1 halt);
+        let pattern = [InstructionMatcher::AnyPush, InstructionMatcher::AnyPush];
+        assert!(vec![0, 2] == program.find_pattern(&pattern));
+
+        let halt = InstructionMatcher::Exact(Instruction::Halt);
+        let pattern = [halt, InstructionMatcher::Any];
+        assert!(program.find_pattern(&pattern).is_empty());
+        assert!(program.find_pattern(&[]).is_empty());
+    }
+
+    #[test]
+    fn extracted_subroutine_runs_in_isolation() {
+        let program = triton_program!(
+            push 1 pop 1 call triple halt
+            triple: read_io 1 push 3 mul write_io 1 return
+            unused: push 2 return
+        );
+        let subroutine = program.extract_subroutine("triple").unwrap();
+        let expected =
+            triton_program!(call triple halt triple: read_io 1 push 3 mul write_io 1 return);
+        assert!(expected == subroutine);
+
+        let output = subroutine.run(bfe_vec![5].into(), [].into()).unwrap();
+        assert!(bfe_vec![15] == output);
+    }
+
+    #[test]
+    fn extracted_subroutine_keeps_recursive_calls_and_guarded_returns() {
+        let program = triton_program!(
+            read_io 1 call countdown halt
+            countdown:
+                dup 0 push 0 eq skiz return
+                dup 0 write_io 1 push -1 add
+                call countdown
+                return
+        );
+        let subroutine = program.extract_subroutine("countdown").unwrap();
+        let expected = triton_program!(
+            call countdown halt
+            countdown:
+                dup 0 push 0 eq skiz return
+                dup 0 write_io 1 push -1 add
+                call countdown
+                return
+        );
+        assert!(expected == subroutine);
+        assert!(subroutine.run([].into(), [].into()).is_ok());
+    }
+
+    #[test]
+    fn extracting_subroutine_with_call_to_other_subroutine_fails() {
+        let program = triton_program!(call foo halt foo: call bar return bar: return);
+        let_assert!(Err(err) = program.extract_subroutine("foo"));
+        let_assert!(SubroutineExtractionError::CallOutsideSubroutine { address, target } = err);
+        assert!(3 == address);
+        assert!(6 == target);
+    }
+
+    #[test]
+    fn extracting_unknown_or_non_returning_subroutine_fails() {
+        let program = triton_program!(call foo halt foo: push 1);
+        let_assert!(Err(err) = program.extract_subroutine("bar"));
+        assert!(SubroutineExtractionError::UnknownLabel("bar".to_string()) == err);
+
+        let_assert!(Err(err) = program.extract_subroutine("foo"));
+        assert!(SubroutineExtractionError::MissingReturn("foo".to_string()) == err);
+    }
+
+    #[test]
+    fn program_built_with_builder_runs_a_loop() {
+        let program = ProgramBuilder::new()
+            .push(3)
+            .call("countdown")
+            .instruction(AnInstruction::Halt)
+            .label("countdown")
+            .instruction(AnInstruction::Dup(OpStackElement::ST0))
+            .instruction(AnInstruction::WriteIo(NumberOfWords::N1))
+            .push(-1)
+            .instruction(AnInstruction::Add)
+            .instruction(AnInstruction::Dup(OpStackElement::ST0))
+            .push(0)
+            .instruction(AnInstruction::Eq)
+            .instruction(AnInstruction::Skiz)
+            .instruction(AnInstruction::Return)
+            .instruction(AnInstruction::Recurse)
+            .build()
+            .unwrap();
+
+        let expected_program = triton_program! {
+            push 3 call countdown halt
+            countdown:
+                dup 0 write_io 1
+                push -1 add
+                dup 0 push 0 eq skiz return
+                recurse
+        };
+        assert!(expected_program == program);
+
+        let_assert!(Ok(output) = program.run([].into(), [].into()));
+        assert!(bfe_vec![3, 2, 1] == output);
+    }
+
+    #[test]
+    fn minimizing_failing_input_keeps_only_the_culprit() {
+        let program = triton_program!(
+            read_io 1
+            call skip_tokens
+            divine 1 push 17 eq push 0 eq assert halt
+            skip_tokens:
+                dup 0 push 0 eq skiz return
+                divine 1 pop 1 addi -1 recurse
+        );
+        let mut failing_secret = bfe_vec![5; 10];
+        failing_secret[4] = bfe!(17);
+        let minimized =
+            program.minimize_failing_input(bfe_vec![4].into(), failing_secret, Program::MAX_CYCLES);
+
+        // the crash happens at the same `assert` even if the skipped tokens are zero
+        assert!(bfe_vec![0, 0, 0, 0, 17] == minimized);
+    }
+
+    #[test]
+    fn minimizing_input_of_successful_run_leaves_input_unchanged() {
+        let program = triton_program!(divine 2 pop 2 halt);
+        let secret = bfe_vec![1, 2, 3];
+        let minimized = program.minimize_failing_input([].into(), secret.clone(), 10);
+        assert!(secret == minimized);
+    }
+
+    #[test]
+    fn minimizing_input_of_non_halting_run_leaves_input_unchanged() {
+        let program = triton_program!(divine 2 pop 2 call forever halt forever: nop recurse);
+        let secret = bfe_vec![1, 2, 3];
+        let minimized = program.minimize_failing_input([].into(), secret.clone(), 100);
+        assert!(secret == minimized);
+    }
+
+    #[test]
+    fn retracing_after_ram_edit_is_identical_to_full_rerun() {
+        let program = triton_program!(
+            push 1 push 2 add pop 1
+            push 42 read_mem 1 pop 1
+            write_io 1 halt
+        );
+        let trace = |ram_value: u64| {
+            let non_determinism = NonDeterminism::default().with_ram([(bfe!(42), bfe!(ram_value))]);
+            let initial_state = VMState::new(&program, [].into(), non_determinism);
+            program
+                .retrace_from(&[initial_state], 0, Program::MAX_CYCLES)
+                .unwrap()
+        };
+
+        let prior = trace(1);
+        let full_rerun = trace(2);
+        assert!(prior.last().unwrap().public_output == bfe_vec![1]);
+        assert!(full_rerun.last().unwrap().public_output == bfe_vec![2]);
+
+        let changed_cycle = 4;
+        let mut edited = prior.clone();
+        edited[changed_cycle].ram.insert(bfe!(42), bfe!(2));
+        let retraced = program
+            .retrace_from(&edited, changed_cycle, Program::MAX_CYCLES)
+            .unwrap();
+
+        assert!(prior[..changed_cycle] == retraced[..changed_cycle]);
+        assert!(full_rerun[changed_cycle..] == retraced[changed_cycle..]);
+    }
+
+    #[test]
+    fn retracing_without_edit_reproduces_prior_trace() {
+        let program = FIBONACCI_SEQUENCE.clone();
+        let initial_state = VMState::new(&program, bfe_vec![7].into(), [].into());
+        let prior = program
+            .retrace_from(&[initial_state], 0, Program::MAX_CYCLES)
+            .unwrap();
+        for changed_cycle in [0, 1, prior.len() / 2, prior.len() - 1] {
+            let retraced = program
+                .retrace_from(&prior, changed_cycle, Program::MAX_CYCLES)
+                .unwrap();
+            assert!(prior == retraced);
+        }
+    }
+
+    #[test]
+    fn retracing_from_out_of_bounds_cycle_is_an_error() {
+        let program = triton_program!(halt);
+        let initial_state = VMState::new(&program, [].into(), [].into());
+        let err = program
+            .retrace_from(&[initial_state], 1, Program::MAX_CYCLES)
+            .unwrap_err();
+        let_assert!(
+            RetraceError::ChangedCycleOutOfBounds {
+                changed_cycle,
+                num_prior_states
+            } = err
+        );
+        assert!(1 == changed_cycle);
+        assert!(1 == num_prior_states);
+    }
+
+    #[test]
+    fn retracing_non_halting_program_exceeds_cycle_limit() {
+        let program = triton_program!(call forever halt forever: nop recurse);
+        let initial_state = VMState::new(&program, [].into(), [].into());
+        let max_cycles = 100;
+        let err = program
+            .retrace_from(&[initial_state], 0, max_cycles)
+            .unwrap_err();
+        let_assert!(RetraceError::VMError(err) = err);
+        assert!(InstructionError::CycleLimitExceeded(max_cycles) == err.source);
+    }
+
+    #[test]
+    fn program_builder_rejects_invalid_labels() {
+        let duplicate_label = ProgramBuilder::new().label("foo").label("foo").build();
+        let_assert!(Err(ProgramBuildingError::DuplicateLabel(label)) = duplicate_label);
+        assert!("foo" == label);
+
+        let undefined_label = ProgramBuilder::new().call("bar").build();
+        let_assert!(Err(ProgramBuildingError::UndefinedLabel(label)) = undefined_label);
+        assert!("bar" == label);
+    }
+
+    #[test]
+    fn program_builder_rejects_pushing_address_of_undefined_label() {
+        let undefined_label = ProgramBuilder::new().push_label_address("foo").build();
+        let_assert!(Err(ProgramBuildingError::UndefinedLabel(label)) = undefined_label);
+        assert!("foo" == label);
+    }
+
+    #[test]
+    fn program_builder_can_push_label_address() {
+        let program = ProgramBuilder::new()
+            .push_label_address("foo")
+            .instruction(AnInstruction::Halt)
+            .label("foo")
+            .instruction(AnInstruction::Return)
+            .build()
+            .unwrap();
+        assert!(triton_program!(push 3 halt foo: return) == program);
+    }
+
+    #[derive(Debug, Default, Copy, Clone)]
+    struct CountingTraceSink {
+        num_states: usize,
+        num_co_processor_calls: usize,
+    }
+
+    impl TraceSink for CountingTraceSink {
+        type Error = Infallible;
+
+        fn record_state(&mut self, _: &VMState) -> Result<(), Self::Error> {
+            self.num_states += 1;
+            Ok(())
+        }
+
+        fn record_co_processor_call(&mut self, _: CoProcessorCall) -> Result<(), Self::Error> {
+            self.num_co_processor_calls += 1;
+            Ok(())
+        }
+    }
+
+    /// Fails once the given number of states has been recorded, like a sink running out of
+    /// memory.
+    #[derive(Debug, Copy, Clone)]
+    struct BoundedTraceSink {
+        capacity: usize,
+    }
+
+    impl TraceSink for BoundedTraceSink {
+        type Error = io::Error;
+
+        fn record_state(&mut self, _: &VMState) -> Result<(), Self::Error> {
+            let Some(capacity) = self.capacity.checked_sub(1) else {
+                return Err(io::ErrorKind::OutOfMemory.into());
+            };
+            self.capacity = capacity;
+            Ok(())
+        }
+
+        fn record_co_processor_call(&mut self, _: CoProcessorCall) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn failure_of_trace_sink_aborts_tracing() {
+        let program = FIBONACCI_SEQUENCE.clone();
+        let mut sink = BoundedTraceSink { capacity: 10 };
+        let_assert!(
+            Err(err) = program.trace_execution_into(bfe_vec![7].into(), [].into(), &mut sink)
+        );
+        let_assert!(TracingError::SinkFailure { source, vm_state } = err);
+        assert!(io::ErrorKind::OutOfMemory == source.kind());
+        assert!(10 == vm_state.cycle_count);
+    }
+
+    #[test]
+    fn trace_sink_records_one_state_per_cycle() {
+        let program = CALCULATE_NEW_MMR_PEAKS_FROM_APPEND_WITH_SAFE_LISTS.clone();
+        let mut sink = CountingTraceSink::default();
+        let_assert!(Ok(output) = program.trace_execution_into([].into(), [].into(), &mut sink));
+
+        let initial_state = VMState::new(&program, [].into(), [].into());
+        let (_, terminal_state) = program.trace_execution_of_state(initial_state).unwrap();
+        assert!(terminal_state.public_output == output);
+        assert!(terminal_state.cycle_count as usize == sink.num_states);
+
+        let mut aet =
+            AlgebraicExecutionTrace::new(program.clone()).with_co_processor_call_recording();
+        program
+            .trace_execution_into([].into(), [].into(), &mut aet)
+            .unwrap();
+        let_assert!(Some(co_processor_calls) = aet.co_processor_calls());
+        assert!(co_processor_calls.len() == sink.num_co_processor_calls);
+    }
+
+    #[test]
+    fn bounded_run_agrees_with_unbounded_run_if_within_bounds() {
+        let program = triton_program!(push 3 push 4 add write_io 1 halt);
+        let output = program.run([].into(), [].into()).unwrap();
+        let_assert!(Ok(bounded_output) = program.run_bounded([].into(), [].into(), 5));
+        assert!(output == bounded_output);
+    }
+
+    #[test]
+    fn bounded_execution_of_infinite_loop_aborts_with_cycle_limit_exceeded() {
+        let program = triton_program!(call forever halt forever: nop recurse);
+        let max_cycles = 100;
+
+        let_assert!(Err(err) = program.run_bounded([].into(), [].into(), max_cycles));
+        assert!(InstructionError::CycleLimitExceeded(max_cycles) == err.source);
+        assert!(max_cycles == err.vm_state.cycle_count);
+
+        let trace_result = program.trace_execution_bounded([].into(), [].into(), max_cycles);
+        let_assert!(Err(err) = trace_result);
+        assert!(InstructionError::CycleLimitExceeded(max_cycles) == err.source);
+        assert!(max_cycles == err.vm_state.cycle_count);
+    }
+
+    #[test]
+    fn running_beyond_max_cycles_results_in_error() {
+        let program = triton_program!(nop halt);
+        let mut state = VMState::new(&program, [].into(), [].into());
+        state.cycle_count = Program::MAX_CYCLES;
+
+        let_assert!(Err(err) = state.clone().run());
+        assert!(InstructionError::CycleLimitExceeded(Program::MAX_CYCLES) == err);
+
+        let_assert!(Err(err) = program.trace_execution_of_state(state));
+        let expected_err = InstructionError::CycleLimitExceeded(Program::MAX_CYCLES);
+        assert!(expected_err == err.source);
+    }
+
+    #[test]
+    fn max_cycles_is_limited_by_cycle_counter() {
+        let state = VMState::new(&triton_program!(halt), [].into(), [].into());
+        let max_cycle_count = state.cycle_count.wrapping_sub(1);
+        assert!(max_cycle_count == Program::MAX_CYCLES);
+    }
+
+    #[test]
+    fn bounded_trace_execution_aborts_when_exceeding_cycle_limit() {
+        let program = CALCULATE_NEW_MMR_PEAKS_FROM_APPEND_WITH_SAFE_LISTS.clone();
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        let max_cycles = u32::try_from(aet.processor_trace.nrows()).unwrap() - 1;
+
+        let_assert!(Err(err) = program.trace_execution_bounded([].into(), [].into(), max_cycles));
+        let_assert!(InstructionError::CycleLimitExceeded(limit) = err.source);
+        assert!(max_cycles == limit);
+        assert!(max_cycles == err.vm_state.cycle_count);
+    }
+
+    #[proptest]
+    fn running_with_input_source_agrees_with_regular_run(
+        #[strategy(arb())] input: [BFieldElement; 4],
+    ) {
+        let program = triton_program! {
+            read_io 1 read_io 3 add add add write_io 1 halt
+        };
+        let output = program.run(input.into(), [].into()).unwrap();
+
+        let mut input_stream = input.into_iter();
+        let streamed_output = program
+            .run_with_input_source(move || input_stream.next(), [].into())
+            .unwrap();
+        prop_assert_eq!(&output, &streamed_output);
+
+        let input_source = VecDeque::from(input.to_vec());
+        let (_, traced_output) = program
+            .trace_execution_with_input_source(input_source, [].into())
+            .unwrap();
+        prop_assert_eq!(output, traced_output);
+    }
+
+    #[test]
+    fn input_is_pulled_from_input_source_only_when_needed() {
+        let program = triton_program!(read_io 1 pop 1 halt);
+        let mut num_pulls = 0;
+        let input_source = || {
+            num_pulls += 1;
+            Some(bfe!(42))
+        };
+        program
+            .run_with_input_source(input_source, [].into())
+            .unwrap();
+        assert!(1 == num_pulls);
+    }
+
+    #[test]
+    fn exhausting_input_source_gives_same_error_as_exhausting_public_input() {
+        let program = triton_program!(read_io 2 read_io 3 halt);
+        let input = bfe_vec![1, 2, 3, 4];
+
+        let_assert!(Err(err) = program.run(input.clone().into(), [].into()));
+        let_assert!(
+            Err(streamed_err) = program.run_with_input_source(VecDeque::from(input), [].into())
+        );
+        let_assert!(
+            InstructionError::InputExhausted {
+                kind,
+                requested,
+                available
+            } = err.source
+        );
+        assert!(InputKind::Public == kind);
+        assert!(3 == requested);
+        assert!(2 == available);
+        assert!(err.source == streamed_err.source);
+    }
+
+    #[proptest]
+    fn running_with_secret_source_agrees_with_regular_run(
+        #[strategy(arb())] secret_input: [BFieldElement; 4],
+    ) {
+        let program = triton_program! {
+            divine 3 divine 1 add add add write_io 1 halt
+        };
+        let output = program.run([].into(), secret_input.into()).unwrap();
+        let streamed_output = program
+            .run_with_secret_source([].into(), secret_input.into_iter())
+            .unwrap();
+        prop_assert_eq!(output, streamed_output);
+    }
+
+    #[test]
+    fn secret_input_is_pulled_from_iterator_only_when_needed() {
+        let program = triton_program!(divine 2 pop 2 halt);
+        let mut num_pulls = 0;
+        let secret_input = std::iter::repeat_with(|| {
+            num_pulls += 1;
+            bfe!(42)
+        });
+        program
+            .run_with_secret_source([].into(), secret_input)
+            .unwrap();
+        assert!(2 == num_pulls);
+    }
+
+    #[test]
+    fn exhausting_secret_source_gives_same_error_as_exhausting_secret_input() {
+        let program = triton_program!(divine 4 divine 2 halt);
+        let secret_input = bfe_vec![1, 2, 3, 4, 5];
+
+        let_assert!(Err(err) = program.run([].into(), secret_input.clone().into()));
+        let_assert!(
+            Err(streamed_err) = program.run_with_secret_source([].into(), secret_input.into_iter())
+        );
+        let_assert!(
+            InstructionError::InputExhausted {
+                kind,
+                requested,
+                available
+            } = err.source
+        );
+        assert!(InputKind::Secret == kind);
+        assert!(2 == requested);
+        assert!(1 == available);
+        assert!(err.source == streamed_err.source);
+    }
+
     #[test]
     fn program_with_too_many_returns_crashes_vm_but_not_profiler() {
         let program = triton_program! {
@@ -1115,4 +4188,58 @@ mod tests {
         let program = Program::decode(&encoding).unwrap();
         println!("{program}");
     }
+
+    #[test]
+    fn programs_differing_only_in_label_names_and_breakpoints_are_equal() {
+        let program = triton_program! {
+            call foo halt
+            foo: break push 1 skiz call bar return
+            bar: push 2 pop 1 return
+        };
+        let renamed = triton_program! {
+            call address_3 halt
+            address_3: push 1 skiz call address_9 return
+            address_9: push 2 pop 1 return
+        };
+        assert!(program == renamed);
+        assert!(renamed == program);
+    }
+
+    #[test]
+    fn programs_with_different_control_flow_are_not_equal() {
+        let program = triton_program! {
+            call foo halt
+            foo: push 1 return
+            bar: push 2 return
+        };
+        let other_target = triton_program! {
+            call bar halt
+            foo: push 1 return
+            bar: push 2 return
+        };
+        assert!(program != other_target);
+    }
+
+    #[test]
+    fn completion_candidates_contain_all_mnemonics_and_labels() {
+        let program = triton_program! {
+            call foo halt
+            foo: call bar return
+            bar: push 1 pop 1 return
+        };
+        let candidates = program.completion_candidates();
+
+        for mnemonic in ALL_INSTRUCTION_NAMES
+            .into_iter()
+            .chain(PseudoInstruction::ALL_NAMES)
+        {
+            assert!(candidates.contains(&mnemonic.to_string()));
+        }
+        for label in ["foo", "bar"] {
+            assert!(candidates.contains(&label.to_string()));
+        }
+
+        let num_mnemonics = ALL_INSTRUCTION_NAMES.len() + PseudoInstruction::ALL_NAMES.len();
+        assert!(num_mnemonics + 2 == candidates.len());
+    }
 }
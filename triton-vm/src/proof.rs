@@ -5,8 +5,13 @@ use serde::Deserialize;
 use serde::Serialize;
 use twenty_first::prelude::*;
 
+use crate::error::ProofDecodingError;
 use crate::error::ProofStreamError;
+use crate::error::VMError;
+use crate::program::NonDeterminism;
 use crate::program::Program;
+use crate::program::PublicInput;
+use crate::proof_item::ProofItem;
 use crate::proof_stream::ProofStream;
 
 /// Contains the necessary cryptographic information to verify a computation.
@@ -19,19 +24,116 @@ impl Proof {
     /// This is an upper bound on the length of the computation this proof is for.
     /// It is one of the main contributing factors to the length of the FRI domain.
     pub fn padded_height(&self) -> Result<usize, ProofStreamError> {
+        let log_2_padded_height = self.unique_item(
+            ProofItem::try_into_log2_padded_height,
+            ProofStreamError::NoLog2PaddedHeight,
+            ProofStreamError::TooManyLog2PaddedHeights,
+        )?;
+        Ok(1 << log_2_padded_height)
+    }
+
+    /// The length of the FRI domain used during proof generation.
+    pub fn fri_domain_length(&self) -> Result<usize, ProofStreamError> {
+        let log_2_fri_domain_length = self.unique_item(
+            ProofItem::try_into_log2_fri_domain_length,
+            ProofStreamError::NoLog2FriDomainLength,
+            ProofStreamError::TooManyLog2FriDomainLengths,
+        )?;
+        1_usize.checked_shl(log_2_fri_domain_length).ok_or(
+            ProofStreamError::FriDomainLengthTooLarge(log_2_fri_domain_length),
+        )
+    }
+
+    /// The FRI expansion factor used during proof generation, _i.e._, the ratio between the
+    /// lengths of the FRI domain and the randomized trace domain.
+    pub fn fri_expansion_factor(&self) -> Result<usize, ProofStreamError> {
+        let fri_expansion_factor = self.unique_item(
+            ProofItem::try_into_fri_expansion_factor,
+            ProofStreamError::NoFriExpansionFactor,
+            ProofStreamError::TooManyFriExpansionFactors,
+        )?;
+        Ok(fri_expansion_factor as usize)
+    }
+
+    fn unique_item<T>(
+        &self,
+        try_into_payload: fn(ProofItem) -> Result<T, ProofStreamError>,
+        missing_item_error: ProofStreamError,
+        duplicate_item_error: ProofStreamError,
+    ) -> Result<T, ProofStreamError> {
         let proof_stream = ProofStream::try_from(self)?;
-        let proof_items = proof_stream.items.into_iter();
-        let log_2_padded_heights = proof_items
-            .filter_map(|item| item.try_into_log2_padded_height().ok())
+        let mut payloads = proof_stream
+            .items
+            .into_iter()
+            .filter_map(|item| try_into_payload(item).ok())
             .collect_vec();
 
-        if log_2_padded_heights.is_empty() {
-            return Err(ProofStreamError::NoLog2PaddedHeight);
+        if payloads.is_empty() {
+            return Err(missing_item_error);
         }
-        if log_2_padded_heights.len() > 1 {
-            return Err(ProofStreamError::TooManyLog2PaddedHeights);
+        if payloads.len() > 1 {
+            return Err(duplicate_item_error);
+        }
+        Ok(payloads.remove(0))
+    }
+
+    /// The version of the [byte format](Self::to_bytes) produced by this version of Triton VM.
+    pub const BYTE_FORMAT_VERSION: u32 = 1;
+
+    const BYTE_FORMAT_MAGIC: [u8; 4] = *b"TVMP";
+
+    const BYTE_FORMAT_HEADER_LEN: usize = 8;
+
+    /// A compact byte representation of the proof, suitable for transmission or storage. It
+    /// consists of an 8-byte header, made up of magic bytes and the
+    /// [format version](Self::BYTE_FORMAT_VERSION), followed by the proof's field elements as
+    /// little-endian `u64`s.
+    ///
+    /// The inverse of [`from_bytes`](Self::from_bytes).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let body_len = self.0.len() * std::mem::size_of::<u64>();
+        let mut bytes = Vec::with_capacity(Self::BYTE_FORMAT_HEADER_LEN + body_len);
+        bytes.extend(Self::BYTE_FORMAT_MAGIC);
+        bytes.extend(Self::BYTE_FORMAT_VERSION.to_le_bytes());
+        for element in &self.0 {
+            bytes.extend(element.value().to_le_bytes());
         }
-        Ok(1 << log_2_padded_heights[0])
+        bytes
+    }
+
+    /// Parse a proof from its [byte representation](Self::to_bytes). Fails on any malformed
+    /// input, including unknown format versions, truncated input, and non-canonical encodings
+    /// of field elements.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofDecodingError> {
+        if bytes.len() < Self::BYTE_FORMAT_HEADER_LEN {
+            return Err(ProofDecodingError::MissingHeader);
+        }
+        let (header, body) = bytes.split_at(Self::BYTE_FORMAT_HEADER_LEN);
+        let (magic, version) = header.split_at(Self::BYTE_FORMAT_MAGIC.len());
+        if magic != Self::BYTE_FORMAT_MAGIC {
+            return Err(ProofDecodingError::UnexpectedMagicBytes);
+        }
+        let version = u32::from_le_bytes(version.try_into().unwrap());
+        if version != Self::BYTE_FORMAT_VERSION {
+            return Err(ProofDecodingError::UnsupportedVersion(version));
+        }
+
+        let element_len = std::mem::size_of::<u64>();
+        if body.len() % element_len != 0 {
+            return Err(ProofDecodingError::IncompleteElement(body.len()));
+        }
+        let elements = body
+            .chunks_exact(element_len)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .map(|value| {
+                if value >= BFieldElement::P {
+                    return Err(ProofDecodingError::NonCanonicalElement(value));
+                }
+                Ok(BFieldElement::new(value))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self(elements))
     }
 }
 
@@ -65,7 +167,23 @@ impl Claim {
 
     #[must_use]
     pub fn about_program(program: &Program) -> Self {
-        Self::new(program.hash::<Tip5>())
+        Self::new(program.hash_with_vm_hasher())
+    }
+
+    /// The claim about [running](Program::run) the given program on the given input. The
+    /// [output](Self::output) is the output of that run.
+    pub fn about(
+        program: &Program,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> Result<Self, VMError> {
+        let input = public_input.individual_tokens.clone();
+        let output = program.run(public_input, non_determinism)?;
+        let claim = Self::about_program(program)
+            .with_input(input)
+            .with_output(output);
+
+        Ok(claim)
     }
 
     #[must_use]
@@ -84,12 +202,14 @@ impl Claim {
 #[cfg(test)]
 mod tests {
     use assert2::assert;
+    use assert2::let_assert;
     use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
     use test_strategy::proptest;
 
-    use crate::proof_item::ProofItem;
+    use crate::shared_tests::prove_with_low_security_level;
+    use crate::triton_program;
 
     use super::*;
 
@@ -134,6 +254,122 @@ mod tests {
         assert!(maybe_padded_height.is_err());
     }
 
+    #[proptest]
+    fn proof_bytes_round_trip(#[strategy(arb())] proof: Proof) {
+        let bytes = proof.to_bytes();
+        prop_assert_eq!(proof, Proof::from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn claim_about_program_run_is_claim_of_proof() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let (stark, claim, proof) =
+            prove_with_low_security_level(&program, bfe_vec![21].into(), [].into(), 2);
+
+        let claim_about_run = Claim::about(&program, bfe_vec![21].into(), [].into()).unwrap();
+        assert!(claim == claim_about_run);
+        assert!(let Ok(()) = stark.verify(&claim_about_run, &proof));
+
+        let mismatched_claim = Claim::about(&program, bfe_vec![20].into(), [].into()).unwrap();
+        assert!(let Err(_) = stark.verify(&mismatched_claim, &proof));
+    }
+
+    #[test]
+    fn claim_about_crashing_program_run_is_error() {
+        let program = triton_program!(push 0 assert halt);
+        assert!(let Err(_) = Claim::about(&program, [].into(), [].into()));
+    }
+
+    #[test]
+    fn bytes_of_real_proof_round_trip() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let (stark, claim, proof) =
+            prove_with_low_security_level(&program, bfe_vec![21].into(), [].into(), 2);
+
+        let bytes = proof.to_bytes();
+        assert!(8 + 8 * proof.0.len() == bytes.len());
+
+        let decoded_proof = Proof::from_bytes(&bytes).unwrap();
+        assert!(proof == decoded_proof);
+        assert!(let Ok(()) = stark.verify(&claim, &decoded_proof));
+    }
+
+    #[test]
+    fn fri_parameters_of_real_proof_survive_byte_round_trip() {
+        let program = triton_program!(halt);
+        let log_2_fri_expansion_factor = 2;
+        let (stark, _, proof) = prove_with_low_security_level(
+            &program,
+            [].into(),
+            [].into(),
+            log_2_fri_expansion_factor,
+        );
+
+        let_assert!(Ok(padded_height) = proof.padded_height());
+        let fri = stark.derive_fri(padded_height).unwrap();
+        let_assert!(Ok(fri_domain_length) = proof.fri_domain_length());
+        let_assert!(Ok(fri_expansion_factor) = proof.fri_expansion_factor());
+        assert!(fri.domain.length == fri_domain_length);
+        assert!(1 << log_2_fri_expansion_factor == fri_expansion_factor);
+
+        let decoded_proof = Proof::from_bytes(&proof.to_bytes()).unwrap();
+        let_assert!(Ok(decoded_fri_domain_length) = decoded_proof.fri_domain_length());
+        let_assert!(Ok(decoded_fri_expansion_factor) = decoded_proof.fri_expansion_factor());
+        assert!(fri_domain_length == decoded_fri_domain_length);
+        assert!(fri_expansion_factor == decoded_fri_expansion_factor);
+    }
+
+    #[test]
+    fn proof_without_fri_parameters_gives_err() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(8));
+        let proof: Proof = proof_stream.into();
+
+        let_assert!(Err(ProofStreamError::NoLog2FriDomainLength) = proof.fri_domain_length());
+        let_assert!(Err(ProofStreamError::NoFriExpansionFactor) = proof.fri_expansion_factor());
+    }
+
+    #[test]
+    fn proof_with_too_large_fri_domain_length_gives_err() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::Log2FriDomainLength(u32::MAX));
+        let proof: Proof = proof_stream.into();
+
+        let_assert!(Err(err) = proof.fri_domain_length());
+        let_assert!(ProofStreamError::FriDomainLengthTooLarge(u32::MAX) = err);
+    }
+
+    #[test]
+    fn malformed_proof_bytes_give_appropriate_errors() {
+        let bytes = Proof(bfe_vec![1, 2, 3]).to_bytes();
+
+        let_assert!(Err(err) = Proof::from_bytes(&bytes[..5]));
+        assert!(ProofDecodingError::MissingHeader == err);
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] ^= 1;
+        let_assert!(Err(err) = Proof::from_bytes(&bad_magic));
+        assert!(ProofDecodingError::UnexpectedMagicBytes == err);
+
+        let mut bad_version = bytes.clone();
+        bad_version[4] = 0;
+        let_assert!(Err(err) = Proof::from_bytes(&bad_version));
+        assert!(ProofDecodingError::UnsupportedVersion(0) == err);
+
+        let_assert!(Err(err) = Proof::from_bytes(&bytes[..bytes.len() - 1]));
+        assert!(ProofDecodingError::IncompleteElement(23) == err);
+
+        let mut non_canonical = bytes;
+        non_canonical[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        let_assert!(Err(err) = Proof::from_bytes(&non_canonical));
+        assert!(ProofDecodingError::NonCanonicalElement(u64::MAX) == err);
+    }
+
+    #[proptest]
+    fn parsing_arbitrary_bytes_does_not_panic(#[strategy(vec(arb(), 0..1_000))] bytes: Vec<u8>) {
+        let _proof = Proof::from_bytes(&bytes);
+    }
+
     #[proptest]
     fn decoding_arbitrary_proof_data_does_not_panic(
         #[strategy(vec(arb(), 0..1_000))] proof_data: Vec<BFieldElement>,
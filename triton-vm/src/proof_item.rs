@@ -112,6 +112,8 @@ proof_items!(
     FriCodeword(Vec<XFieldElement>) => false, try_into_fri_codeword,
     FriPolynomial(Polynomial<XFieldElement>) => false, try_into_fri_polynomial,
     FriResponse(FriResponse) => false, try_into_fri_response,
+    Log2FriDomainLength(u32) => false, try_into_log2_fri_domain_length,
+    FriExpansionFactor(u32) => false, try_into_fri_expansion_factor,
 );
 
 #[cfg(test)]
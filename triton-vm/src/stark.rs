@@ -1,5 +1,7 @@
+use std::collections::HashMap;
 use std::ops::Mul;
 use std::ops::MulAssign;
+use std::sync::Mutex;
 
 use arbitrary::Arbitrary;
 use arbitrary::Unstructured;
@@ -8,9 +10,16 @@ use itertools::Itertools;
 use ndarray::prelude::*;
 use ndarray::Zip;
 use num_traits::Zero;
+use rand::random;
+use rand::rngs::StdRng;
+use rand::CryptoRng;
+use rand::Rng;
+use rand::SeedableRng;
 use rayon::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
+use strum::EnumCount;
+use strum::EnumIter;
 use twenty_first::math::ntt::intt;
 use twenty_first::math::traits::FiniteField;
 use twenty_first::math::traits::PrimitiveRootOfUnity;
@@ -18,6 +27,8 @@ use twenty_first::prelude::*;
 
 use crate::aet::AlgebraicExecutionTrace;
 use crate::arithmetic_domain::ArithmeticDomain;
+use crate::error::ConstraintViolation;
+use crate::error::ProofStreamError;
 use crate::error::ProvingError;
 use crate::error::VerificationError;
 use crate::fri;
@@ -32,11 +43,15 @@ use crate::table::extension_table::Evaluable;
 use crate::table::extension_table::Quotientable;
 use crate::table::master_table::all_quotients_combined;
 use crate::table::master_table::interpolant_degree;
+use crate::table::master_table::locate_constraint;
 use crate::table::master_table::max_degree_with_origin;
+use crate::table::master_table::randomize_trace_with_seed;
 use crate::table::master_table::MasterBaseTable;
 use crate::table::master_table::MasterExtTable;
 use crate::table::master_table::MasterTable;
+use crate::table::master_table::RandomnessSeed;
 use crate::table::master_table::AIR_TARGET_DEGREE;
+use crate::table::ConstraintType;
 use crate::table::QuotientSegments;
 use crate::table::NUM_BASE_COLUMNS;
 use crate::table::NUM_EXT_COLUMNS;
@@ -51,6 +66,45 @@ pub const NUM_RANDOMIZER_POLYNOMIALS: usize = 1;
 
 const NUM_DEEP_CODEWORD_COMPONENTS: usize = 3;
 
+/// A milestone of [proof generation](Stark::prove_with_progress), reported once it is reached.
+/// The stages are listed in the order in which they are reached.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, EnumCount, EnumIter)]
+pub enum ProveStage {
+    /// The execution trace has been filled into the master base table and padded.
+    TraceFilled,
+
+    /// All columns of the master base table have been low-degree extended.
+    BaseTableLowDegreeExtended,
+
+    /// The master base table has been committed to.
+    BaseTableCommitted,
+
+    /// The master extension table has been computed, low-degree extended, and committed to.
+    ExtTableCommitted,
+
+    /// The quotient segments have been computed and committed to.
+    QuotientsCommitted,
+
+    /// All rounds of [FRI](Fri) have been completed.
+    FriCompleted,
+
+    /// The proof is complete.
+    Done,
+}
+
+/// How thoroughly [`Stark::constraint_violations`] looks for violated AIR constraints.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ConstraintCheckMode {
+    /// Stop at the first violated constraint. Fast, useful for gating.
+    #[default]
+    FirstViolation,
+
+    /// Report every violated constraint in every row. Thorough, useful when developing
+    /// new constraints.
+    AllViolations,
+}
+
 /// The Zero-Knowledge [Scalable Transparent ARgument of Knowledge (STARK)][stark] for Triton VM.
 ///
 /// [stark]: https://www.iacr.org/archive/crypto2019/116940201/116940201.pdf
@@ -100,11 +154,60 @@ impl Stark {
         }
     }
 
+    /// Change the conjectured security level, in bits, keeping the FRI expansion factor. All
+    /// parameters depending on the security level are re-derived.
+    ///
+    /// Lower security levels result in faster proving and smaller proofs, which can be useful
+    /// for testing. For production use, the [default](Self::default) is highly recommended.
+    #[must_use]
+    pub fn with_security_level(self, security_level: usize) -> Self {
+        let log2_of_fri_expansion_factor = self.fri_expansion_factor.ilog2() as usize;
+        Self::new(security_level, log2_of_fri_expansion_factor)
+    }
+
     pub fn prove(
         &self,
         claim: &Claim,
         aet: &AlgebraicExecutionTrace,
     ) -> Result<Proof, ProvingError> {
+        self.prove_with_progress(claim, aet, |_| ())
+    }
+
+    /// Like [`prove`](Self::prove), but reports the completion of every [`ProveStage`] to the
+    /// given callback. Useful for displaying progress, since proving can take a while.
+    pub fn prove_with_progress<F: FnMut(ProveStage)>(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        progress: F,
+    ) -> Result<Proof, ProvingError> {
+        self.prove_with_randomness_seed(claim, aet, progress, random())
+    }
+
+    /// Like [`prove`](Self::prove), but all randomness required for zero-knowledge is drawn from
+    /// the given random number generator. Proving twice with identically seeded generators
+    /// produces identical proofs, which is useful for reproducible tests.
+    ///
+    /// Since the proof's zero-knowledge property depends on it, the random number generator
+    /// must be cryptographically secure.
+    pub fn prove_with_rng<R: Rng + CryptoRng>(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        rng: &mut R,
+    ) -> Result<Proof, ProvingError> {
+        self.prove_with_randomness_seed(claim, aet, |_| (), rng.gen())
+    }
+
+    fn prove_with_randomness_seed<F: FnMut(ProveStage)>(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        mut progress: F,
+        randomness_seed: RandomnessSeed,
+    ) -> Result<Proof, ProvingError> {
+        let mut rng = StdRng::from_seed(randomness_seed);
+
         profiler!(start "Fiat-Shamir: claim" ("hash"));
         let mut proof_stream = ProofStream::new();
         proof_stream.alter_fiat_shamir_state_with(claim);
@@ -116,6 +219,9 @@ impl Stark {
         let fri = self.derive_fri(padded_height)?;
         let quotient_domain = Self::quotient_domain(fri.domain, max_degree)?;
         proof_stream.enqueue(ProofItem::Log2PaddedHeight(padded_height.ilog2()));
+        proof_stream.enqueue(ProofItem::Log2FriDomainLength(fri.domain.length.ilog2()));
+        let fri_expansion_factor = fri.expansion_factor.try_into().unwrap();
+        proof_stream.enqueue(ProofItem::FriExpansionFactor(fri_expansion_factor));
         profiler!(stop "derive additional parameters");
 
         profiler!(start "base tables");
@@ -127,18 +233,21 @@ impl Stark {
         profiler!(start "pad" ("gen"));
         master_base_table.pad();
         profiler!(stop "pad");
+        progress(ProveStage::TraceFilled);
 
         profiler!(start "randomize trace" ("gen"));
-        master_base_table.randomize_trace();
+        randomize_trace_with_seed(&mut master_base_table, rng.gen());
         profiler!(stop "randomize trace");
 
         profiler!(start "LDE" ("LDE"));
         master_base_table.low_degree_extend_all_columns();
         profiler!(stop "LDE");
+        progress(ProveStage::BaseTableLowDegreeExtended);
 
         profiler!(start "Merkle tree" ("hash"));
         let base_merkle_tree = master_base_table.merkle_tree();
         profiler!(stop "Merkle tree");
+        progress(ProveStage::BaseTableCommitted);
 
         profiler!(start "Fiat-Shamir" ("hash"));
         proof_stream.enqueue(ProofItem::MerkleRoot(base_merkle_tree.root()));
@@ -147,13 +256,14 @@ impl Stark {
         profiler!(stop "Fiat-Shamir");
 
         profiler!(start "extend" ("gen"));
-        let mut master_ext_table = master_base_table.extend(&challenges);
+        let mut master_ext_table =
+            master_base_table.extend_with_randomness_seed(&challenges, rng.gen());
         profiler!(stop "extend");
         profiler!(stop "base tables");
 
         profiler!(start "ext tables");
         profiler!(start "randomize trace" ("gen"));
-        master_ext_table.randomize_trace();
+        randomize_trace_with_seed(&mut master_ext_table, rng.gen());
         profiler!(stop "randomize trace");
 
         profiler!(start "LDE" ("LDE"));
@@ -163,6 +273,7 @@ impl Stark {
         profiler!(start "Merkle tree" ("hash"));
         let ext_merkle_tree = master_ext_table.merkle_tree();
         profiler!(stop "Merkle tree");
+        progress(ProveStage::ExtTableCommitted);
 
         profiler!(start "Fiat-Shamir" ("hash"));
         proof_stream.enqueue(ProofItem::MerkleRoot(ext_merkle_tree.root()));
@@ -201,6 +312,7 @@ impl Stark {
         let quot_merkle_tree_root = quot_merkle_tree.root();
         proof_stream.enqueue(ProofItem::MerkleRoot(quot_merkle_tree_root));
         profiler!(stop "Merkle tree");
+        progress(ProveStage::QuotientsCommitted);
 
         debug_assert_eq!(fri.domain.length, quot_merkle_tree.num_leafs());
 
@@ -357,6 +469,7 @@ impl Stark {
             revealed_current_row_indices.len()
         );
         profiler!(stop "FRI");
+        progress(ProveStage::FriCompleted);
 
         profiler!(start "open trace leafs");
         // Open leafs of zipped codewords at indicated positions
@@ -411,10 +524,173 @@ impl Stark {
             revealed_quotient_authentication_structure,
         ));
         profiler!(stop "open trace leafs");
+        progress(ProveStage::Done);
 
         Ok(proof_stream.into())
     }
 
+    /// Like [`prove`](Self::prove), but first makes sure the execution trace satisfies all
+    /// AIR constraints. A trace violating some constraint leads to a proof that fails
+    /// verification, which would otherwise only be noticed after the expensive proof
+    /// generation has finished.
+    pub fn prove_checked(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+    ) -> Result<Proof, ProvingError> {
+        self.check_constraints(claim, aet)?;
+        self.prove(claim, aet)
+    }
+
+    /// Evaluate all AIR constraints on the padded, but not randomized, execution trace.
+    /// Reports the first violated constraint, if any. Constraints of type
+    /// [initial](ConstraintType::Initial) are checked first, followed by
+    /// [consistency](ConstraintType::Consistency), [transition](ConstraintType::Transition),
+    /// and [terminal](ConstraintType::Terminal) constraints.
+    pub fn check_constraints(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+    ) -> Result<(), ProvingError> {
+        let mode = ConstraintCheckMode::FirstViolation;
+        let violations = self.constraint_violations(claim, aet, mode)?;
+        match violations.into_iter().next() {
+            Some(violation) => Err(violation.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Evaluate all AIR constraints on the padded, but not randomized, execution trace and
+    /// list the violated ones in the order described in
+    /// [`check_constraints`](Self::check_constraints). Depending on the given `mode`, the
+    /// list contains at most the first violation, or all of them.
+    pub fn constraint_violations(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        mode: ConstraintCheckMode,
+    ) -> Result<Vec<ConstraintViolation>, ProvingError> {
+        let padded_height = aet.padded_height();
+        let max_degree = self.derive_max_degree(padded_height);
+        let fri = self.derive_fri(padded_height)?;
+        let quotient_domain = Self::quotient_domain(fri.domain, max_degree)?;
+        let mut master_base_table =
+            MasterBaseTable::new(aet, self.num_trace_randomizers, quotient_domain, fri.domain);
+        master_base_table.pad();
+
+        let mut proof_stream = ProofStream::new();
+        proof_stream.alter_fiat_shamir_state_with(claim);
+        let challenges = proof_stream.sample_scalars(Challenges::SAMPLE_COUNT);
+        let challenges = Challenges::new(challenges, claim);
+        let master_ext_table = master_base_table.extend(&challenges);
+
+        let base_trace = master_base_table.trace_table();
+        let ext_trace = master_ext_table.trace_table();
+        let last_row_index = base_trace.nrows() - 1;
+        let mut violations = vec![];
+
+        let initial_evaluations = MasterExtTable::evaluate_initial_constraints(
+            base_trace.row(0),
+            ext_trace.row(0),
+            &challenges,
+        );
+        let constraint_type = ConstraintType::Initial;
+        Self::record_violations(
+            &mut violations,
+            initial_evaluations,
+            constraint_type,
+            0,
+            mode,
+        );
+        if Self::is_done_checking(&violations, mode) {
+            return Ok(violations);
+        }
+
+        for row_index in 0..=last_row_index {
+            let consistency_evaluations = MasterExtTable::evaluate_consistency_constraints(
+                base_trace.row(row_index),
+                ext_trace.row(row_index),
+                &challenges,
+            );
+            let constraint_type = ConstraintType::Consistency;
+            Self::record_violations(
+                &mut violations,
+                consistency_evaluations,
+                constraint_type,
+                row_index,
+                mode,
+            );
+            if Self::is_done_checking(&violations, mode) {
+                return Ok(violations);
+            }
+        }
+
+        for row_index in 0..last_row_index {
+            let transition_evaluations = MasterExtTable::evaluate_transition_constraints(
+                base_trace.row(row_index),
+                ext_trace.row(row_index),
+                base_trace.row(row_index + 1),
+                ext_trace.row(row_index + 1),
+                &challenges,
+            );
+            let constraint_type = ConstraintType::Transition;
+            Self::record_violations(
+                &mut violations,
+                transition_evaluations,
+                constraint_type,
+                row_index,
+                mode,
+            );
+            if Self::is_done_checking(&violations, mode) {
+                return Ok(violations);
+            }
+        }
+
+        let terminal_evaluations = MasterExtTable::evaluate_terminal_constraints(
+            base_trace.row(last_row_index),
+            ext_trace.row(last_row_index),
+            &challenges,
+        );
+        let constraint_type = ConstraintType::Terminal;
+        Self::record_violations(
+            &mut violations,
+            terminal_evaluations,
+            constraint_type,
+            last_row_index,
+            mode,
+        );
+        Ok(violations)
+    }
+
+    fn record_violations(
+        violations: &mut Vec<ConstraintViolation>,
+        evaluated_constraints: Vec<XFieldElement>,
+        constraint_type: ConstraintType,
+        row_index: usize,
+        mode: ConstraintCheckMode,
+    ) {
+        let violated_indices = evaluated_constraints.iter().positions(|c| !c.is_zero());
+        let new_violations = violated_indices.map(|constraint_index| {
+            let (table, table_constraint_index) =
+                locate_constraint(constraint_type, constraint_index);
+            ConstraintViolation {
+                constraint_type,
+                constraint_index,
+                table,
+                table_constraint_index,
+                row_index,
+            }
+        });
+        match mode {
+            ConstraintCheckMode::FirstViolation => violations.extend(new_violations.take(1)),
+            ConstraintCheckMode::AllViolations => violations.extend(new_violations),
+        }
+    }
+
+    fn is_done_checking(violations: &[ConstraintViolation], mode: ConstraintCheckMode) -> bool {
+        mode == ConstraintCheckMode::FirstViolation && !violations.is_empty()
+    }
+
     fn compute_quotient_segments(
         master_base_table: &MasterBaseTable,
         master_ext_table: &MasterExtTable,
@@ -583,6 +859,28 @@ impl Stark {
         )
     }
 
+    /// The largest padded height of an [`AlgebraicExecutionTrace`] this STARK can prove. For
+    /// larger traces, some [domain](ArithmeticDomain) required for proving is longer than the
+    /// field supports. Since the processor table has one row per cycle, plus one, this also
+    /// bounds the number of cycles a provable program can run for, possibly more tightly than
+    /// [`Program::MAX_CYCLES`](crate::program::Program::MAX_CYCLES).
+    pub fn max_padded_height(&self) -> usize {
+        let can_prove = |padded_height| {
+            let Ok(fri) = self.derive_fri(padded_height) else {
+                return false;
+            };
+            let max_degree = self.derive_max_degree(padded_height);
+            Self::quotient_domain(fri.domain, max_degree).is_ok()
+        };
+
+        // the field's two-adicity is 32; longer domains are never supported
+        (0..=32)
+            .rev()
+            .filter_map(|log2_padded_height| 1_usize.checked_shl(log2_padded_height))
+            .find(|&padded_height| can_prove(padded_height))
+            .unwrap_or(0)
+    }
+
     /// Read the indicated rows from the cached table. The indices come from FRI.
     fn read_revealed_rows<const N: usize, FF: FiniteField>(
         fri_domain_table: ArrayView2<FF>,
@@ -722,7 +1020,71 @@ impl Stark {
         segments.try_into().unwrap()
     }
 
+    /// Verify multiple proofs independently of each other, in parallel. Each proof is verified
+    /// against the claim it is paired with; the result at index `i` is for the pair at index `i`.
+    ///
+    /// The verifier's setup that depends only on the padded height, like [FRI](Fri) and the
+    /// involved [domains](ArithmeticDomain), is derived once per padded height and shared across
+    /// the batch.
+    pub fn verify_batch(
+        &self,
+        claims_and_proofs: &[(Claim, Proof)],
+    ) -> Vec<Result<(), VerificationError>> {
+        let setups = Mutex::new(HashMap::new());
+        let derive_setup = |padded_height| {
+            let cached_setup = setups.lock().unwrap().get(&padded_height).cloned();
+            if let Some(setup) = cached_setup {
+                return Ok(setup);
+            }
+            let setup = self.derive_verifier_setup(padded_height)?;
+            setups.lock().unwrap().insert(padded_height, setup.clone());
+            Ok(setup)
+        };
+
+        claims_and_proofs
+            .par_iter()
+            .map(|(claim, proof)| self.verify_with_setup(claim, proof, false, derive_setup))
+            .collect()
+    }
+
     pub fn verify(&self, claim: &Claim, proof: &Proof) -> Result<(), VerificationError> {
+        let derive_setup = |padded_height| self.derive_verifier_setup(padded_height);
+        self.verify_with_setup(claim, proof, false, derive_setup)
+    }
+
+    /// Like [`verify`](Self::verify), but intended for debugging failing verification. Each of
+    /// the verifier's checks reports its own [`VerificationError`]. Additionally, failing
+    /// [FRI](Fri) is attributed to the first failing FRI round, see
+    /// [`Fri::first_failing_round`]. This requires replaying FRI verification, which regular
+    /// verification does not do.
+    pub fn verify_with_transcript(
+        &self,
+        claim: &Claim,
+        proof: &Proof,
+    ) -> Result<(), VerificationError> {
+        let derive_setup = |padded_height| self.derive_verifier_setup(padded_height);
+        self.verify_with_setup(claim, proof, true, derive_setup)
+    }
+
+    fn derive_verifier_setup(
+        &self,
+        padded_height: usize,
+    ) -> Result<VerifierSetup, VerificationError> {
+        let fri = self.derive_fri(padded_height)?;
+        let trace_domain_generator = ArithmeticDomain::generator_for_length(padded_height as u64)?;
+        Ok(VerifierSetup {
+            fri,
+            trace_domain_generator,
+        })
+    }
+
+    fn verify_with_setup(
+        &self,
+        claim: &Claim,
+        proof: &Proof,
+        locate_fri_failure: bool,
+        derive_setup: impl Fn(usize) -> Result<VerifierSetup, VerificationError>,
+    ) -> Result<(), VerificationError> {
         profiler!(start "deserialize");
         let mut proof_stream = ProofStream::try_from(proof)?;
         profiler!(stop "deserialize");
@@ -734,8 +1096,30 @@ impl Stark {
         profiler!(start "derive additional parameters");
         let log_2_padded_height = proof_stream.dequeue()?.try_into_log2_padded_height()?;
         let padded_height = 1 << log_2_padded_height;
-        let fri = self.derive_fri(padded_height)?;
+        let VerifierSetup {
+            fri,
+            trace_domain_generator,
+        } = derive_setup(padded_height)?;
         let merkle_tree_height = fri.domain.length.ilog2() as usize;
+
+        let log_2_fri_domain_length = proof_stream.dequeue()?.try_into_log2_fri_domain_length()?;
+        let proof_fri_domain_length = 1_usize.checked_shl(log_2_fri_domain_length).ok_or(
+            ProofStreamError::FriDomainLengthTooLarge(log_2_fri_domain_length),
+        )?;
+        if proof_fri_domain_length != fri.domain.length {
+            return Err(VerificationError::FriDomainLengthMismatch {
+                proof: proof_fri_domain_length,
+                expected: fri.domain.length,
+            });
+        }
+        let proof_fri_expansion_factor = proof_stream.dequeue()?.try_into_fri_expansion_factor()?;
+        let proof_fri_expansion_factor = proof_fri_expansion_factor as usize;
+        if proof_fri_expansion_factor != fri.expansion_factor {
+            return Err(VerificationError::FriExpansionFactorMismatch {
+                proof: proof_fri_expansion_factor,
+                expected: fri.expansion_factor,
+            });
+        }
         profiler!(stop "derive additional parameters");
 
         profiler!(start "Fiat-Shamir 1" ("hash"));
@@ -751,7 +1135,6 @@ impl Stark {
         profiler!(stop "Fiat-Shamir 1");
 
         profiler!(start "dequeue ood point and rows" ("hash"));
-        let trace_domain_generator = ArithmeticDomain::generator_for_length(padded_height as u64)?;
         let out_of_domain_point_curr_row = proof_stream.sample_scalars(1)[0];
         let out_of_domain_point_next_row = trace_domain_generator * out_of_domain_point_curr_row;
         let out_of_domain_point_curr_row_pow_num_segments =
@@ -868,7 +1251,19 @@ impl Stark {
 
         // verify low degree of combination polynomial with FRI
         profiler!(start "FRI");
-        let revealed_fri_indices_and_elements = fri.verify(&mut proof_stream)?;
+        let fri_proof_stream = locate_fri_failure.then(|| proof_stream.clone());
+        let revealed_fri_indices_and_elements = match fri.verify(&mut proof_stream) {
+            Ok(revealed_indices_and_elements) => revealed_indices_and_elements,
+            Err(err) => {
+                let Some(mut fri_proof_stream) = fri_proof_stream else {
+                    return Err(err.into());
+                };
+                let Some((round, source)) = fri.first_failing_round(&mut fri_proof_stream) else {
+                    return Err(err.into());
+                };
+                return Err(VerificationError::FriRoundFailure { round, source });
+            }
+        };
         let (revealed_current_row_indices, revealed_fri_values): (Vec<_>, Vec<_>) =
             revealed_fri_indices_and_elements.into_iter().unzip();
         profiler!(stop "FRI");
@@ -1267,6 +1662,13 @@ impl<'a> Arbitrary<'a> for Stark {
     }
 }
 
+/// The parts of the verifier's setup that depend only on the padded height.
+#[derive(Debug, Clone)]
+struct VerifierSetup {
+    fri: Fri<Tip5>,
+    trace_domain_generator: BFieldElement,
+}
+
 /// Fiat-Shamir-sampled challenges to compress a row into a single
 /// [extension field element][XFieldElement].
 struct LinearCombinationWeights {
@@ -1323,10 +1725,12 @@ pub(crate) mod tests {
     use rand::thread_rng;
     use rand::Rng;
     use strum::EnumCount;
+    use strum::IntoEnumIterator;
     use test_strategy::proptest;
     use twenty_first::math::other::random_elements;
     use twenty_first::prelude::x_field_element::EXTENSION_DEGREE;
 
+    use crate::error::FriValidationError;
     use crate::error::InstructionError;
     use crate::example_programs::*;
     use crate::instruction::AnInstruction;
@@ -1597,7 +2001,7 @@ pub(crate) mod tests {
             .arg()
             .map(|_| next_instruction_or_arg.to_string())
             .unwrap_or_default();
-        (curr_instruction.name().to_string(), nia)
+        (curr_instruction.mnemonic().to_string(), nia)
     }
 
     /// To be used with `-- --nocapture`. Has mainly informative purpose.
@@ -2593,6 +2997,248 @@ pub(crate) mod tests {
         assert!(let Ok(()) = stark.verify(&claim, &proof));
     }
 
+    #[test]
+    fn verifier_with_different_fri_expansion_factor_reports_fri_parameter_mismatch() {
+        let program = triton_program!(halt);
+        let (stark, claim, proof) = prove_with_low_security_level(
+            &program,
+            [].into(),
+            [].into(),
+            DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS,
+        );
+
+        let fri_expansion_factor = 2 * stark.fri_expansion_factor;
+        let other_stark = Stark {
+            fri_expansion_factor,
+            ..stark
+        };
+        let_assert!(Err(err) = other_stark.verify(&claim, &proof));
+        let_assert!(
+            VerificationError::FriDomainLengthMismatch {
+                proof: proof_len,
+                expected
+            } = err
+        );
+        assert!(2 * proof_len == expected);
+    }
+
+    #[test]
+    fn verifying_proof_with_too_large_fri_domain_length_gives_err() {
+        let program = triton_program!(halt);
+        let (stark, claim, proof) = prove_with_low_security_level(
+            &program,
+            [].into(),
+            [].into(),
+            DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS,
+        );
+
+        let mut proof_stream = ProofStream::try_from(&proof).unwrap();
+        for item in &mut proof_stream.items {
+            if let ProofItem::Log2FriDomainLength(log_2_length) = item {
+                *log_2_length = 64;
+            }
+        }
+        let proof = Proof::from(proof_stream);
+
+        let_assert!(Err(err) = stark.verify(&claim, &proof));
+        let_assert!(VerificationError::ProofStreamError(err) = err);
+        let_assert!(ProofStreamError::FriDomainLengthTooLarge(64) = err);
+    }
+
+    #[test]
+    fn verification_transcript_attributes_fri_failure_to_failing_round() {
+        let program = triton_program!(halt);
+        let (stark, claim, proof) = prove_with_low_security_level(
+            &program,
+            [].into(),
+            [].into(),
+            DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS,
+        );
+
+        let mut proof_stream = ProofStream::try_from(&proof).unwrap();
+        let mut fri_responses = proof_stream.items.iter_mut().filter_map(|item| match item {
+            ProofItem::FriResponse(fri_response) => Some(fri_response),
+            _ => None,
+        });
+        let first_round_response = fri_responses.next().unwrap();
+        for leaf in &mut first_round_response.revealed_leaves {
+            *leaf += xfe!(1);
+        }
+        let proof = proof_stream.into();
+
+        let_assert!(Err(err) = stark.verify(&claim, &proof));
+        let_assert!(VerificationError::FriValidationError(_) = err);
+
+        let_assert!(Err(err) = stark.verify_with_transcript(&claim, &proof));
+        let_assert!(VerificationError::FriRoundFailure { round, source } = err);
+        assert!(0 == round);
+        let_assert!(FriValidationError::BadMerkleAuthenticationPath = source);
+    }
+
+    #[test]
+    fn verification_transcript_accepts_valid_proof() {
+        let program = triton_program!(push 3 push 4 mul write_io 1 halt);
+        let (stark, claim, proof) = prove_with_low_security_level(
+            &program,
+            [].into(),
+            [].into(),
+            DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS,
+        );
+        assert!(let Ok(()) = stark.verify_with_transcript(&claim, &proof));
+    }
+
+    #[test]
+    fn batch_verification_flags_exactly_the_tampered_proof() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let mut claims_and_proofs = [3, 5, 7]
+            .into_iter()
+            .map(|input| {
+                let (_, claim, proof) = prove_with_low_security_level(
+                    &program,
+                    bfe_vec![input].into(),
+                    [].into(),
+                    DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS,
+                );
+                (claim, proof)
+            })
+            .collect_vec();
+
+        let (_, tampered_proof) = &mut claims_and_proofs[1];
+        let last_element = tampered_proof.0.last_mut().unwrap();
+        *last_element += bfe!(1);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let results = stark.verify_batch(&claims_and_proofs);
+        assert!(3 == results.len());
+        assert!(let Ok(()) = results[0]);
+        assert!(let Err(_) = results[1]);
+        assert!(let Ok(()) = results[2]);
+    }
+
+    #[test]
+    fn changing_security_level_re_derives_dependent_parameters() {
+        let stark = Stark::new(160, 3).with_security_level(48);
+        assert!(Stark::new(48, 3) == stark);
+        assert!(Stark::default() == stark.with_security_level(160).with_security_level(160));
+    }
+
+    #[test]
+    fn max_padded_height_is_largest_power_of_two_with_supported_domains() {
+        let stark = Stark::default();
+        let max_padded_height = stark.max_padded_height();
+        assert!(max_padded_height.is_power_of_two());
+        assert!(max_padded_height <= 1 << 32);
+
+        let_assert!(Ok(fri) = stark.derive_fri(max_padded_height));
+        let max_degree = stark.derive_max_degree(max_padded_height);
+        assert!(let Ok(_) = Stark::quotient_domain(fri.domain, max_degree));
+
+        let too_large_height = 2 * max_padded_height;
+        let domains_are_supported = stark.derive_fri(too_large_height).is_ok_and(|fri| {
+            let max_degree = stark.derive_max_degree(too_large_height);
+            Stark::quotient_domain(fri.domain, max_degree).is_ok()
+        });
+        assert!(!domains_are_supported);
+    }
+
+    #[test]
+    fn larger_fri_expansion_factor_does_not_increase_max_padded_height() {
+        let max_padded_height = Stark::new(160, 2).max_padded_height();
+        assert!(Stark::new(160, 4).max_padded_height() <= max_padded_height);
+    }
+
+    #[test]
+    fn proving_with_identically_seeded_rngs_produces_identical_proofs() {
+        let program = triton_program!(push 3 push 4 mul write_io 1 halt);
+        let (aet, output) = program.trace_execution([].into(), [].into()).unwrap();
+        let claim = Claim::about_program(&program).with_output(output);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+
+        let prove_with_seed = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            stark.prove_with_rng(&claim, &aet, &mut rng).unwrap()
+        };
+        let proof = prove_with_seed(42);
+        assert!(proof == prove_with_seed(42));
+        assert!(proof != prove_with_seed(43));
+        assert!(let Ok(()) = stark.verify(&claim, &proof));
+    }
+
+    #[test]
+    fn prove_checked_on_valid_trace_produces_verifiable_proof() {
+        let program = triton_program!(push 3 push 4 add write_io 1 halt);
+        let (aet, output) = program.trace_execution([].into(), [].into()).unwrap();
+        let claim = Claim::about_program(&program).with_output(output);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let_assert!(Ok(proof) = stark.prove_checked(&claim, &aet));
+        assert!(let Ok(()) = stark.verify(&claim, &proof));
+    }
+
+    #[test]
+    fn prove_checked_reports_terminal_constraint_violation_for_wrong_output() {
+        let program = triton_program!(push 3 push 4 add write_io 1 halt);
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        let claim = Claim::about_program(&program).with_output(bfe_vec![8]);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let_assert!(Err(err) = stark.prove_checked(&claim, &aet));
+        let_assert!(ProvingError::ConstraintViolation(violation) = err);
+        assert!(ConstraintType::Terminal == violation.constraint_type);
+        assert!(violation.table.is_none());
+    }
+
+    #[test]
+    fn collecting_all_constraint_violations_finds_more_than_first_violation() {
+        let program = triton_program!(read_io 1 write_io 1 halt);
+        let (aet, _) = program
+            .trace_execution(bfe_vec![5].into(), [].into())
+            .unwrap();
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![6])
+            .with_output(bfe_vec![7]);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let first_mode = ConstraintCheckMode::FirstViolation;
+        let_assert!(Ok(first) = stark.constraint_violations(&claim, &aet, first_mode));
+        let all_mode = ConstraintCheckMode::AllViolations;
+        let_assert!(Ok(all) = stark.constraint_violations(&claim, &aet, all_mode));
+
+        assert!(1 == first.len());
+        assert!(2 == all.len());
+        assert!(first[0] == all[0]);
+        for violation in all {
+            assert!(ConstraintType::Terminal == violation.constraint_type);
+        }
+    }
+
+    #[test]
+    fn valid_trace_has_no_constraint_violations() {
+        let program = triton_program!(push 3 push 4 add write_io 1 halt);
+        let (aet, output) = program.trace_execution([].into(), [].into()).unwrap();
+        let claim = Claim::about_program(&program).with_output(output);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let mode = ConstraintCheckMode::AllViolations;
+        let_assert!(Ok(violations) = stark.constraint_violations(&claim, &aet, mode));
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn progress_callback_reports_every_stage_in_order() {
+        let program = triton_program!(push 3 push 4 add write_io 1 halt);
+        let (aet, output) = program.trace_execution([].into(), [].into()).unwrap();
+        let claim = Claim::about_program(&program).with_output(output);
+
+        let mut stages = vec![];
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark
+            .prove_with_progress(&claim, &aet, |stage| stages.push(stage))
+            .unwrap();
+        assert!(ProveStage::iter().collect_vec() == stages);
+        assert!(let Ok(()) = stark.verify(&claim, &proof));
+    }
+
     fn prove_verify_halt_prop(log2_expansion_factor: usize) {
         let code_with_input = test_program_for_halt();
 
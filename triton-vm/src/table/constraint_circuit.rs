@@ -34,6 +34,8 @@ use quote::quote;
 use quote::ToTokens;
 use twenty_first::prelude::*;
 
+use crate::table::master_table::all_constraint_degrees;
+
 use CircuitExpression::*;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -913,14 +915,43 @@ impl<II: InputIndicator> ConstraintCircuitMonad<II> {
 
     /// Returns the maximum degree of all circuits in the multicircuit.
     pub(crate) fn multicircuit_degree(multicircuit: &[ConstraintCircuitMonad<II>]) -> isize {
+        Self::degrees(multicircuit).into_iter().max().unwrap_or(-1)
+    }
+
+    /// Returns the degree of every circuit in the multicircuit.
+    pub(crate) fn degrees(multicircuit: &[ConstraintCircuitMonad<II>]) -> Vec<isize> {
         multicircuit
             .iter()
             .map(|circuit| circuit.circuit.borrow().degree())
-            .max()
-            .unwrap_or(-1)
+            .collect()
     }
 }
 
+/// The maximum degree of all of Triton VM's AIR constraints before automatic degree lowering.
+/// This degree, not the [target degree](crate::table::master_table::AIR_TARGET_DEGREE), is what
+/// changes when a constraint is modified.
+///
+/// See also [`degrees_by_table`].
+pub fn max_degree() -> isize {
+    degrees_by_table()
+        .into_iter()
+        .flat_map(|(_, degrees)| degrees)
+        .max()
+        .unwrap_or(-1)
+}
+
+/// The degree of each of Triton VM's AIR constraints before automatic degree lowering, grouped by
+/// table. Within a table, the degrees of the initial, consistency, transition, and terminal
+/// constraints are listed in that order.
+///
+/// See also [`max_degree`] and [`constraint_summary`](crate::table::master_table::constraint_summary).
+pub fn degrees_by_table() -> Vec<(&'static str, Vec<isize>)> {
+    all_constraint_degrees()
+        .into_iter()
+        .map(|(table_name, degrees)| (table_name, degrees.concat()))
+        .collect()
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 /// Helper struct to construct new leaf nodes in the circuit multitree. Ensures that each newly
 /// created node gets a unique ID.
@@ -2204,4 +2235,31 @@ mod tests {
         let o = ch(0) * z1 - ch(1) * w;
         assert!(!o.find_equivalent_nodes().is_empty());
     }
+
+    #[test]
+    fn degrees_by_table_agree_with_constraint_summary() {
+        let degrees_by_table = degrees_by_table();
+        let summary = constraint_summary();
+        assert_eq!(summary.len(), degrees_by_table.len());
+
+        for (info, (table_name, degrees)) in summary.into_iter().zip(degrees_by_table) {
+            assert_eq!(info.table_name, table_name);
+            let num_constraints = info.num_initial_constraints
+                + info.num_consistency_constraints
+                + info.num_transition_constraints
+                + info.num_terminal_constraints;
+            assert_eq!(num_constraints, degrees.len());
+            let table_max_degree = degrees.into_iter().max().unwrap_or(-1);
+            assert_eq!(info.max_degree, table_max_degree);
+        }
+    }
+
+    #[test]
+    fn max_degree_is_maximum_of_all_constraint_degrees() {
+        let max_degree = max_degree();
+        assert!(max_degree > AIR_TARGET_DEGREE);
+        for (_, degrees) in degrees_by_table() {
+            assert!(degrees.into_iter().all(|degree| degree <= max_degree));
+        }
+    }
 }
@@ -5,6 +5,7 @@ use std::ops::MulAssign;
 use std::ops::Range;
 
 use itertools::Itertools;
+use lazy_static::lazy_static;
 use master_table::extension_table::Evaluable;
 use ndarray::parallel::prelude::*;
 use ndarray::prelude::*;
@@ -18,9 +19,13 @@ use num_traits::Zero;
 use rand::distributions::Standard;
 use rand::prelude::Distribution;
 use rand::random;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
 use strum::Display;
 use strum::EnumCount;
 use strum::EnumIter;
+use strum::IntoEnumIterator;
 use twenty_first::math::tip5::DIGEST_LENGTH;
 use twenty_first::math::tip5::RATE;
 use twenty_first::math::traits::FiniteField;
@@ -37,19 +42,31 @@ use crate::ndarray_helper::partial_sums;
 use crate::profiler::profiler;
 use crate::stark::NUM_RANDOMIZER_POLYNOMIALS;
 use crate::table::cascade_table::CascadeTable;
+use crate::table::cascade_table::ExtCascadeTable;
 use crate::table::challenges::Challenges;
+use crate::table::constraint_circuit::ConstraintCircuitBuilder;
+use crate::table::constraint_circuit::ConstraintCircuitMonad;
+use crate::table::cross_table_argument::GrandCrossTableArg;
 use crate::table::degree_lowering_table::DegreeLoweringTable;
 use crate::table::extension_table::all_degrees_with_origin;
 use crate::table::extension_table::DegreeWithOrigin;
 use crate::table::extension_table::Quotientable;
+use crate::table::hash_table::ExtHashTable;
 use crate::table::hash_table::HashTable;
+use crate::table::jump_stack_table::ExtJumpStackTable;
 use crate::table::jump_stack_table::JumpStackTable;
+use crate::table::lookup_table::ExtLookupTable;
 use crate::table::lookup_table::LookupTable;
+use crate::table::op_stack_table::ExtOpStackTable;
 use crate::table::op_stack_table::OpStackTable;
+use crate::table::processor_table::ExtProcessorTable;
 use crate::table::processor_table::ProcessorTable;
+use crate::table::program_table::ExtProgramTable;
 use crate::table::program_table::ProgramTable;
+use crate::table::ram_table::ExtRamTable;
 use crate::table::ram_table::RamTable;
 use crate::table::table_column::*;
+use crate::table::u32_table::ExtU32Table;
 use crate::table::u32_table::U32Table;
 use crate::table::*;
 
@@ -169,6 +186,52 @@ pub enum TableId {
     DegreeLowering,
 }
 
+/// The seed from which all randomness of a [`MasterTable`] is derived.
+pub(crate) type RandomnessSeed = <StdRng as SeedableRng>::Seed;
+
+/// Fill the given array with pseudorandom elements derived from the given seed. Every column
+/// gets its own pseudorandom number generator, allowing to fill the columns in parallel.
+fn fill_with_pseudorandom_elements<FF>(mut array: ArrayViewMut2<FF>, seed: RandomnessSeed)
+where
+    FF: Send,
+    Standard: Distribution<FF>,
+{
+    let mut rng = StdRng::from_seed(seed);
+    let column_seeds = (0..array.ncols())
+        .map(|_| rng.gen::<RandomnessSeed>())
+        .collect::<Array1<_>>();
+    Zip::from(array.axis_iter_mut(Axis(1)))
+        .and(&column_seeds)
+        .par_for_each(|mut column, &column_seed| {
+            let mut column_rng = StdRng::from_seed(column_seed);
+            column
+                .iter_mut()
+                .for_each(|element| *element = column_rng.gen());
+        });
+}
+
+/// Set all rows _not_ part of the actual (padded) trace of the given table to pseudorandom
+/// values, derived from the given seed. See [`MasterTable::randomize_trace`].
+pub(crate) fn randomize_trace_with_seed<FF>(
+    table: &mut (impl MasterTable<FF> + ?Sized),
+    seed: RandomnessSeed,
+) where
+    FF: FiniteField
+        + MulAssign<BFieldElement>
+        + From<BFieldElement>
+        + BFieldCodec
+        + Mul<BFieldElement, Output = FF>,
+    Standard: Distribution<FF>,
+{
+    let unit_distance = table.randomized_trace_domain().length / table.trace_domain().length;
+    let mut rng = StdRng::from_seed(seed);
+    for offset in 1..unit_distance {
+        let mut randomized_trace_table = table.randomized_trace_table_mut();
+        let randomizer_rows = randomized_trace_table.slice_mut(s![offset..; unit_distance, ..]);
+        fill_with_pseudorandom_elements(randomizer_rows, rng.gen::<RandomnessSeed>());
+    }
+}
+
 /// A Master Table is, in some sense, a top-level table of Triton VM. It contains all the data
 /// but little logic beyond bookkeeping and presenting the data in useful ways. Conversely, the
 /// individual tables contain no data but all the respective logic. Master Tables are
@@ -270,12 +333,7 @@ where
 
     /// Set all rows _not_ part of the actual (padded) trace to random values.
     fn randomize_trace(&mut self) {
-        let unit_distance = self.randomized_trace_domain().length / self.trace_domain().length;
-        (1..unit_distance).for_each(|offset| {
-            self.randomized_trace_table_mut()
-                .slice_mut(s![offset..; unit_distance, ..])
-                .par_mapv_inplace(|_| random::<FF>())
-        });
+        randomize_trace_with_seed(self, random::<RandomnessSeed>());
     }
 
     /// Low-degree extend all columns of the randomized trace domain table. The resulting
@@ -785,29 +843,38 @@ impl MasterBaseTable {
             interpolation_polynomials: None,
         };
 
-        // memory-like tables must be filled in before clock jump differences are known, hence
-        // the break from the usual order
-        let clk_jump_diffs_op_stack =
-            OpStackTable::fill_trace(&mut master_base_table.table_mut(TableId::OpStack), aet);
-        let clk_jump_diffs_ram =
-            RamTable::fill_trace(&mut master_base_table.table_mut(TableId::Ram), aet);
-        let clk_jump_diffs_jump_stack =
-            JumpStackTable::fill_trace(&mut master_base_table.table_mut(TableId::JumpStack), aet);
-
-        let processor_table = &mut master_base_table.table_mut(TableId::Processor);
-        ProcessorTable::fill_trace(
-            processor_table,
-            aet,
-            &clk_jump_diffs_op_stack,
-            &clk_jump_diffs_ram,
-            &clk_jump_diffs_jump_stack,
-        );
+        let [mut program, mut processor, mut op_stack, mut ram, mut jump_stack, coprocessors @ ..] =
+            master_base_table.base_tables_mut();
+        let [mut hash, mut cascade, mut lookup, mut u32] = coprocessors;
 
-        ProgramTable::fill_trace(&mut master_base_table.table_mut(TableId::Program), aet);
-        HashTable::fill_trace(&mut master_base_table.table_mut(TableId::Hash), aet);
-        CascadeTable::fill_trace(&mut master_base_table.table_mut(TableId::Cascade), aet);
-        LookupTable::fill_trace(&mut master_base_table.table_mut(TableId::Lookup), aet);
-        U32Table::fill_trace(&mut master_base_table.table_mut(TableId::U32), aet);
+        // The tables occupy disjoint columns and can be filled independently, with one exception:
+        // memory-like tables must be filled in before clock jump differences are known, hence
+        // the Processor Table is filled only after those have been filled.
+        rayon::scope(|scope| {
+            scope.spawn(move |_| ProgramTable::fill_trace(&mut program, aet));
+            scope.spawn(move |_| HashTable::fill_trace(&mut hash, aet));
+            scope.spawn(move |_| CascadeTable::fill_trace(&mut cascade, aet));
+            scope.spawn(move |_| LookupTable::fill_trace(&mut lookup, aet));
+            scope.spawn(move |_| U32Table::fill_trace(&mut u32, aet));
+
+            let (clk_jump_diffs_op_stack, (clk_jump_diffs_ram, clk_jump_diffs_jump_stack)) =
+                rayon::join(
+                    || OpStackTable::fill_trace(&mut op_stack, aet),
+                    || {
+                        rayon::join(
+                            || RamTable::fill_trace(&mut ram, aet),
+                            || JumpStackTable::fill_trace(&mut jump_stack, aet),
+                        )
+                    },
+                );
+            ProcessorTable::fill_trace(
+                &mut processor,
+                aet,
+                &clk_jump_diffs_op_stack,
+                &clk_jump_diffs_ram,
+                &clk_jump_diffs_jump_stack,
+            );
+        });
 
         // Filling the degree-lowering table only makes sense after padding has happened.
         // Hence, this table is omitted here.
@@ -823,27 +890,7 @@ impl MasterBaseTable {
     pub fn pad(&mut self) {
         let table_lengths = self.all_table_lengths();
 
-        let unit_distance = self.randomized_trace_domain().length / self.trace_domain().length;
-        let master_table_without_randomizers = self
-            .randomized_trace_table
-            .slice_mut(s![..; unit_distance, ..]);
-
-        let base_tables: [_; NUM_TABLES_WITHOUT_DEGREE_LOWERING] = horizontal_multi_slice_mut(
-            master_table_without_randomizers,
-            &partial_sums(&[
-                ProgramBaseTableColumn::COUNT,
-                ProcessorBaseTableColumn::COUNT,
-                OpStackBaseTableColumn::COUNT,
-                RamBaseTableColumn::COUNT,
-                JumpStackBaseTableColumn::COUNT,
-                HashBaseTableColumn::COUNT,
-                CascadeBaseTableColumn::COUNT,
-                LookupBaseTableColumn::COUNT,
-                U32BaseTableColumn::COUNT,
-            ]),
-        )
-        .try_into()
-        .unwrap();
+        let base_tables = self.base_tables_mut();
 
         profiler!(start "pad original tables");
         Self::all_pad_functions()
@@ -860,6 +907,29 @@ impl MasterBaseTable {
         profiler!(stop "fill degree-lowering table");
     }
 
+    /// Mutable views of the trace of every table except the degree-lowering table. The views
+    /// are disjoint, which allows working on them in parallel.
+    fn base_tables_mut(
+        &mut self,
+    ) -> [ArrayViewMut2<BFieldElement>; NUM_TABLES_WITHOUT_DEGREE_LOWERING] {
+        horizontal_multi_slice_mut(
+            self.trace_table_mut(),
+            &partial_sums(&[
+                ProgramBaseTableColumn::COUNT,
+                ProcessorBaseTableColumn::COUNT,
+                OpStackBaseTableColumn::COUNT,
+                RamBaseTableColumn::COUNT,
+                JumpStackBaseTableColumn::COUNT,
+                HashBaseTableColumn::COUNT,
+                CascadeBaseTableColumn::COUNT,
+                LookupBaseTableColumn::COUNT,
+                U32BaseTableColumn::COUNT,
+            ]),
+        )
+        .try_into()
+        .unwrap()
+    }
+
     fn all_pad_functions() -> [PadFunction; NUM_TABLES_WITHOUT_DEGREE_LOWERING] {
         [
             ProgramTable::pad_trace,
@@ -895,15 +965,25 @@ impl MasterBaseTable {
     /// table. The `.extend()` for each table is specific to that table, but always involves
     /// adding some number of columns.
     pub fn extend(&self, challenges: &Challenges) -> MasterExtTable {
+        self.extend_with_randomness_seed(challenges, random())
+    }
+
+    /// Like [`extend`](Self::extend), but the randomizer polynomials are derived from the
+    /// given seed.
+    pub(crate) fn extend_with_randomness_seed(
+        &self,
+        challenges: &Challenges,
+        seed: RandomnessSeed,
+    ) -> MasterExtTable {
         // randomizer polynomials
         let num_rows = self.randomized_trace_table().nrows();
         profiler!(start "initialize master table");
         let mut randomized_trace_extension_table =
             fast_zeros_column_major::<XFieldElement>(num_rows, NUM_EXT_COLUMNS);
 
-        randomized_trace_extension_table
-            .slice_mut(s![.., NUM_EXT_COLUMNS_WITHOUT_RANDOMIZER_POLYS..])
-            .par_mapv_inplace(|_| random::<XFieldElement>());
+        let randomizer_polynomials = randomized_trace_extension_table
+            .slice_mut(s![.., NUM_EXT_COLUMNS_WITHOUT_RANDOMIZER_POLYS..]);
+        fill_with_pseudorandom_elements(randomizer_polynomials, seed);
         profiler!(stop "initialize master table");
 
         let mut master_ext_table = MasterExtTable {
@@ -1023,6 +1103,20 @@ impl MasterBaseTable {
             .slice_mut(s![..; unit_distance, column_indices])
     }
 
+    /// [Extend](Self::extend) the already [padded](Self::pad) table, and turn both the base and
+    /// the extension table into [`TraceMatrices`]. Any randomizers are dropped.
+    pub fn into_matrices(self, challenges: &Challenges) -> TraceMatrices {
+        let master_ext_table = self.extend(challenges);
+        let ext_trace_table = master_ext_table.trace_table();
+
+        TraceMatrices {
+            base: self.trace_table().to_owned(),
+            ext: ext_trace_table
+                .slice(s![.., ..NUM_EXT_COLUMNS_WITHOUT_RANDOMIZER_POLYS])
+                .to_owned(),
+        }
+    }
+
     pub(crate) fn try_to_base_row<T: FiniteField>(
         row: Array1<T>,
     ) -> Result<BaseRow<T>, ProvingError> {
@@ -1076,6 +1170,148 @@ impl MasterExtTable {
     }
 }
 
+/// The padded traces of all tables, without any randomizers, detached from the proof system.
+/// Useful for feeding Triton VM's arithmetization into some other commitment scheme.
+///
+/// The rows of both matrices correspond to the cycles of the padded execution trace. Use
+/// [`base_columns`](Self::base_columns) and [`ext_columns`](Self::ext_columns) to find the
+/// columns belonging to some table.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TraceMatrices {
+    pub base: Array2<BFieldElement>,
+    pub ext: Array2<XFieldElement>,
+}
+
+impl TraceMatrices {
+    /// Generate the padded trace matrices of the given execution trace. The
+    /// [`Challenges`] are required to [extend](MasterBaseTable::extend) the base tables.
+    pub fn new(aet: &AlgebraicExecutionTrace, challenges: &Challenges) -> Self {
+        let trace_domain = ArithmeticDomain::of_length(aet.padded_height()).unwrap();
+        let mut master_base_table = MasterBaseTable::new(aet, 0, trace_domain, trace_domain);
+        master_base_table.pad();
+        master_base_table.into_matrices(challenges)
+    }
+
+    /// The height of both matrices.
+    pub fn padded_height(&self) -> usize {
+        self.base.nrows()
+    }
+
+    /// The columns of the [base matrix](Self::base) belonging to the given table.
+    pub fn base_columns(table_id: TableId) -> Range<usize> {
+        MasterBaseTable::column_indices_for_table(table_id)
+    }
+
+    /// The columns of the [extension matrix](Self::ext) belonging to the given table.
+    pub fn ext_columns(table_id: TableId) -> Range<usize> {
+        MasterExtTable::column_indices_for_table(table_id)
+    }
+}
+
+/// The number of AIR constraints of each [type](ConstraintType) for one table, alongside their
+/// maximal degree. The numbers refer to the constraints as they are defined, _i.e._, before
+/// automatic degree lowering. See [`constraint_summary`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TableConstraintInfo {
+    pub table_name: &'static str,
+    pub num_initial_constraints: usize,
+    pub num_consistency_constraints: usize,
+    pub num_transition_constraints: usize,
+    pub num_terminal_constraints: usize,
+
+    /// The maximal degree of all of the table's constraints, or -1 if it has none.
+    pub max_degree: isize,
+}
+
+/// A [`TableConstraintInfo`] for every table, as well as for the
+/// [Grand Cross-Table Argument](GrandCrossTableArg). The information is derived from the
+/// constraint definitions, which makes it useful for studying the AIR.
+pub fn constraint_summary() -> Vec<TableConstraintInfo> {
+    let max_degree = |degrees: &[Vec<isize>]| degrees.iter().flatten().copied().max();
+
+    all_constraint_degrees()
+        .into_iter()
+        .map(|(table_name, degrees)| TableConstraintInfo {
+            table_name,
+            num_initial_constraints: degrees[0].len(),
+            num_consistency_constraints: degrees[1].len(),
+            num_transition_constraints: degrees[2].len(),
+            num_terminal_constraints: degrees[3].len(),
+            max_degree: max_degree(&degrees).unwrap_or(-1),
+        })
+        .collect()
+}
+
+lazy_static! {
+    /// The number of initial, consistency, transition, and terminal constraints of every table,
+    /// followed by those of the [Grand Cross-Table Argument](GrandCrossTableArg). The order is
+    /// the same as in the master table's list of constraints.
+    static ref NUM_CONSTRAINTS_BY_TABLE: Vec<[usize; 4]> = all_constraint_degrees()
+        .into_iter()
+        .map(|(_, degrees)| degrees.map(|d| d.len()))
+        .collect();
+}
+
+/// Find the table that the constraint with the given master-table index belongs to. Returns that
+/// table, alongside the index of the constraint among those of the table of the same
+/// [type](ConstraintType).
+///
+/// Constraints of the [Grand Cross-Table Argument](GrandCrossTableArg) belong to no table and
+/// are reported as `None`. Constraints introduced by degree lowering belong to
+/// [`TableId::DegreeLowering`].
+pub(crate) fn locate_constraint(
+    constraint_type: ConstraintType,
+    constraint_index: usize,
+) -> (Option<TableId>, usize) {
+    let type_index = constraint_type as usize;
+    let tables = TableId::iter()
+        .take(NUM_TABLES_WITHOUT_DEGREE_LOWERING)
+        .map(Some)
+        .chain([None]);
+
+    let mut table_constraint_index = constraint_index;
+    for (table, num_constraints) in tables.zip_eq(NUM_CONSTRAINTS_BY_TABLE.iter()) {
+        if table_constraint_index < num_constraints[type_index] {
+            return (table, table_constraint_index);
+        }
+        table_constraint_index -= num_constraints[type_index];
+    }
+
+    (Some(TableId::DegreeLowering), table_constraint_index)
+}
+
+/// The degree of every constraint before automatic degree lowering, by table. For each table, the
+/// degrees of the initial, consistency, transition, and terminal constraints are listed in that
+/// order.
+pub(crate) fn all_constraint_degrees() -> Vec<(&'static str, [Vec<isize>; 4])> {
+    macro_rules! all_degrees {
+        ($($table:ident: $table_name:literal),* $(,)?) => {
+            vec![$((
+                $table_name,
+                [
+                    ConstraintCircuitMonad::degrees(&$table::initial_constraints(&ConstraintCircuitBuilder::new())),
+                    ConstraintCircuitMonad::degrees(&$table::consistency_constraints(&ConstraintCircuitBuilder::new())),
+                    ConstraintCircuitMonad::degrees(&$table::transition_constraints(&ConstraintCircuitBuilder::new())),
+                    ConstraintCircuitMonad::degrees(&$table::terminal_constraints(&ConstraintCircuitBuilder::new())),
+                ],
+            )),*]
+        };
+    }
+
+    all_degrees!(
+        ExtProgramTable: "ProgramTable",
+        ExtProcessorTable: "ProcessorTable",
+        ExtOpStackTable: "OpStackTable",
+        ExtRamTable: "RamTable",
+        ExtJumpStackTable: "JumpStackTable",
+        ExtHashTable: "HashTable",
+        ExtCascadeTable: "CascadeTable",
+        ExtLookupTable: "LookupTable",
+        ExtU32Table: "U32Table",
+        GrandCrossTableArg: "GrandCrossTableArg",
+    )
+}
+
 pub(crate) fn max_degree_with_origin(
     interpolant_degree: isize,
     padded_height: usize,
@@ -1360,6 +1596,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn trace_matrices_have_padded_height_and_table_widths() {
+        let program = ProgramAndInput::new(triton_program!(push 3 push 4 add pop 1 halt));
+        let (_, claim, mut master_base_table) = master_base_table_for_low_security_level(program);
+        let padded_height = master_base_table.trace_domain().length;
+
+        let challenges = Challenges::placeholder(&claim);
+        master_base_table.pad();
+        let trace_matrices = master_base_table.clone().into_matrices(&challenges);
+
+        assert_eq!(padded_height, trace_matrices.padded_height());
+        assert_eq!(padded_height, trace_matrices.ext.nrows());
+        assert_eq!(NUM_BASE_COLUMNS, trace_matrices.base.ncols());
+        assert_eq!(
+            NUM_EXT_COLUMNS_WITHOUT_RANDOMIZER_POLYS,
+            trace_matrices.ext.ncols()
+        );
+        assert_eq!(master_base_table.trace_table(), trace_matrices.base);
+
+        for table_id in TableId::iter() {
+            let base_columns = TraceMatrices::base_columns(table_id);
+            let ext_columns = TraceMatrices::ext_columns(table_id);
+            let base_table = trace_matrices.base.slice(s![.., base_columns]);
+            let ext_table = trace_matrices.ext.slice(s![.., ext_columns]);
+            assert_eq!(master_base_table.table(table_id), base_table);
+            assert_eq!(padded_height, ext_table.nrows());
+        }
+        assert_eq!(
+            program_table::EXT_WIDTH,
+            TraceMatrices::ext_columns(TableId::Program).len()
+        );
+    }
+
+    #[test]
+    fn trace_matrices_from_execution_trace_agree_with_master_tables() {
+        let program = triton_program!(push 3 push 4 add pop 1 halt);
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        let program_and_input = ProgramAndInput::new(program);
+        let (_, claim, mut master_base_table) =
+            master_base_table_for_low_security_level(program_and_input);
+        let challenges = Challenges::placeholder(&claim);
+
+        let trace_matrices = TraceMatrices::new(&aet, &challenges);
+        assert_eq!(aet.padded_height(), trace_matrices.padded_height());
+        assert_eq!(aet.padded_height(), trace_matrices.ext.nrows());
+        assert_eq!(NUM_BASE_COLUMNS, trace_matrices.base.ncols());
+        assert_eq!(
+            NUM_EXT_COLUMNS_WITHOUT_RANDOMIZER_POLYS,
+            trace_matrices.ext.ncols()
+        );
+
+        let processor_columns = TraceMatrices::base_columns(TableId::Processor);
+        let num_cycles = aet.processor_trace.nrows();
+        let processor_rows = s![..num_cycles, processor_columns];
+        assert_eq!(
+            aet.processor_trace,
+            trace_matrices.base.slice(processor_rows)
+        );
+
+        master_base_table.pad();
+        assert_eq!(master_base_table.into_matrices(&challenges), trace_matrices);
+    }
+
     #[test]
     fn ext_table_width_is_correct() {
         let program = ProgramAndInput::new(triton_program!(halt));
@@ -1943,4 +2242,65 @@ mod tests {
         let expected_digest = Tip5::hash_varlen(&elements);
         prop_assert_eq!(expected_digest, pending_absorb_digest);
     }
+
+    #[test]
+    fn constraint_summary_accounts_for_all_constraints_before_degree_lowering() {
+        let summary = constraint_summary();
+        let total = |num_constraints: fn(&TableConstraintInfo) -> usize| -> usize {
+            summary.iter().map(num_constraints).sum()
+        };
+
+        // degree lowering only ever adds constraints
+        let num_initial = total(|info| info.num_initial_constraints);
+        let num_consistency = total(|info| info.num_consistency_constraints);
+        let num_transition = total(|info| info.num_transition_constraints);
+        let num_terminal = total(|info| info.num_terminal_constraints);
+        assert!(num_initial <= MasterExtTable::NUM_INITIAL_CONSTRAINTS);
+        assert!(num_consistency <= MasterExtTable::NUM_CONSISTENCY_CONSTRAINTS);
+        assert!(num_transition <= MasterExtTable::NUM_TRANSITION_CONSTRAINTS);
+        assert!(num_terminal <= MasterExtTable::NUM_TERMINAL_CONSTRAINTS);
+
+        let max_degree = summary.iter().map(|info| info.max_degree).max().unwrap();
+        assert!(max_degree > AIR_TARGET_DEGREE);
+    }
+
+    #[test]
+    fn constraints_are_located_in_their_tables() {
+        let summary = constraint_summary();
+        let num_program_initial = summary[0].num_initial_constraints;
+        let initial = ConstraintType::Initial;
+        assert!((Some(TableId::Program), 0) == locate_constraint(initial, 0));
+        let (table, index) = locate_constraint(initial, num_program_initial);
+        assert!(Some(TableId::Processor) == table);
+        assert!(0 == index);
+
+        let num_terminal = summary
+            .iter()
+            .map(|i| i.num_terminal_constraints)
+            .sum::<usize>();
+        let (table, index) = locate_constraint(ConstraintType::Terminal, num_terminal - 1);
+        assert!(table.is_none());
+        assert!(summary.last().unwrap().num_terminal_constraints - 1 == index);
+
+        let num_transition = summary
+            .iter()
+            .map(|i| i.num_transition_constraints)
+            .sum::<usize>();
+        let (table, index) = locate_constraint(ConstraintType::Transition, num_transition);
+        assert!(Some(TableId::DegreeLowering) == table);
+        assert!(0 == index);
+    }
+
+    #[test]
+    fn constraint_summary_of_grand_cross_table_argument_contains_only_terminal_constraints() {
+        let summary = constraint_summary();
+        let cross_table_info = summary
+            .iter()
+            .find(|info| info.table_name == "GrandCrossTableArg")
+            .unwrap();
+        assert_eq!(0, cross_table_info.num_initial_constraints);
+        assert_eq!(0, cross_table_info.num_consistency_constraints);
+        assert_eq!(0, cross_table_info.num_transition_constraints);
+        assert!(cross_table_info.num_terminal_constraints > 0);
+    }
 }
@@ -0,0 +1,79 @@
+//! Helpers for testing code built on top of Triton VM. Requires feature `test-support`.
+
+use rand::Rng;
+use twenty_first::prelude::*;
+
+use crate::proof::Claim;
+use crate::proof::Proof;
+use crate::stark::Stark;
+
+/// The number of tampered proofs [`verify_rejects_tampered`] tries to get verified.
+pub const NUM_TAMPERING_ATTEMPTS: usize = 10;
+
+/// Check that the verifier rejects tampered versions of the given proof. A tampered proof is the
+/// given proof with one randomly chosen field element changed to a different value. Returns
+/// `false` if any of [`NUM_TAMPERING_ATTEMPTS`] tampered proofs verifies, which would indicate
+/// a soundness bug.
+///
+/// # Panics
+///
+/// Panics if the given proof is not valid for the given claim to begin with, in which case the
+/// result of tampering would be meaningless.
+pub fn verify_rejects_tampered<R: Rng>(
+    proof: &Proof,
+    claim: &Claim,
+    stark: &Stark,
+    rng: &mut R,
+) -> bool {
+    assert!(
+        stark.verify(claim, proof).is_ok(),
+        "the untampered proof must be valid"
+    );
+
+    for _ in 0..NUM_TAMPERING_ATTEMPTS {
+        let mut tampered_proof = proof.clone();
+        let index = rng.gen_range(0..tampered_proof.0.len());
+        let offset = rng.gen_range(1..BFieldElement::P);
+        tampered_proof.0[index] += BFieldElement::new(offset);
+
+        if stark.verify(claim, &tampered_proof).is_ok() {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::shared_tests::prove_with_low_security_level;
+    use crate::triton_program;
+
+    use super::*;
+
+    #[test]
+    fn verifier_rejects_tampered_proofs() {
+        let program = triton_program!(push 3 push 4 mul write_io 1 halt);
+        let (stark, claim, proof) =
+            prove_with_low_security_level(&program, [].into(), [].into(), 2);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(verify_rejects_tampered(&proof, &claim, &stark, &mut rng));
+    }
+
+    #[test]
+    #[should_panic(expected = "untampered proof must be valid")]
+    fn tampering_with_invalid_proof_panics() {
+        let program = triton_program!(halt);
+        let (stark, claim, proof) =
+            prove_with_low_security_level(&program, [].into(), [].into(), 2);
+        let wrong_claim = claim.with_output(vec![bfe!(42)]);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        verify_rejects_tampered(&proof, &wrong_claim, &stark, &mut rng);
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::fmt::Display;
@@ -11,12 +12,17 @@ use ndarray::Array1;
 use num_traits::One;
 use num_traits::Zero;
 use serde_derive::*;
+use strum::EnumCount;
 use twenty_first::math::x_field_element::EXTENSION_DEGREE;
 use twenty_first::prelude::*;
 use twenty_first::util_types::algebraic_hasher::Domain;
 
+use crate::error::InputKind;
 use crate::error::InstructionError;
 use crate::error::InstructionError::*;
+use crate::error::InvariantViolation;
+use crate::error::StateComponent;
+use crate::error::TransitionError;
 use crate::instruction::AnInstruction::*;
 use crate::instruction::Instruction;
 use crate::op_stack::OpStackElement::*;
@@ -35,6 +41,13 @@ type Result<T> = std::result::Result<T, InstructionError>;
 /// The number of helper variable registers
 pub const NUM_HELPER_VARIABLE_REGISTERS: usize = 6;
 
+/// The hash function Triton VM uses natively, both for instructions like `hash` or
+/// `sponge_absorb` and for computing the digest of the program being executed. Hashing with any
+/// other function gives digests that are inconsistent with Triton VM's program attestation.
+///
+/// See also [`Program::hash_with_vm_hasher`].
+pub type VMHasher = Tip5;
+
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
 pub struct VMState {
     /// The **program memory** stores the instructions (and their arguments) of the program
@@ -53,6 +66,12 @@ pub struct VMState {
     /// A list of [`Digest`]s the program can use for instruction `merkle_step`.
     pub secret_digests: VecDeque<Digest>,
 
+    #[serde(default)]
+    public_input_cursor: usize,
+
+    #[serde(default)]
+    secret_input_cursor: usize,
+
     /// The read-write **random-access memory** allows Triton VM to store arbitrary data.
     pub ram: HashMap<BFieldElement, BFieldElement>,
 
@@ -102,6 +121,76 @@ pub enum CoProcessorCall {
     RamCall(RamTableCall),
 }
 
+/// The differences between two [`VMState`]s, as computed by [`VMState::diff`]. Only includes
+/// the op stack, RAM, and control flow.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct StateDiff {
+    /// For every changed position in the op stack, the old and the new value. `None` indicates
+    /// absence of a value. Positions are counted from the bottom of the stack, _i.e._, index 0 is
+    /// the lowest element in the stack. See also [`OpStack::stack`].
+    pub op_stack: BTreeMap<usize, (Option<BFieldElement>, Option<BFieldElement>)>,
+
+    /// For every changed RAM cell, the old and the new value. Uninitialized RAM has value 0.
+    pub ram: HashMap<BFieldElement, (BFieldElement, BFieldElement)>,
+
+    /// The old and the new instruction pointer, if changed.
+    pub instruction_pointer: Option<(usize, usize)>,
+
+    /// The old and the new jump stack, if changed.
+    pub jump_stack: Option<(
+        Vec<(BFieldElement, BFieldElement)>,
+        Vec<(BFieldElement, BFieldElement)>,
+    )>,
+
+    /// The old and the new halting flag, if changed.
+    pub halting: Option<(bool, bool)>,
+}
+
+impl StateDiff {
+    /// Whether the two compared states agree in all aspects covered by the diff.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Everything a single [step](VMState::step) did, as recorded by [`VMState::step_detailed`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Transition {
+    /// The executed instruction.
+    pub instruction: Instruction,
+
+    /// By how much the op stack grew. Negative if the op stack shrank.
+    pub op_stack_delta: isize,
+
+    /// All reads from and writes to RAM, in the order they happened.
+    pub ram_accesses: Vec<RamTableCall>,
+
+    /// The elements read from the public input.
+    pub public_input_read: Vec<BFieldElement>,
+
+    /// The elements written to the public output.
+    pub public_output_written: Vec<BFieldElement>,
+
+    /// The individual tokens read from the secret input.
+    pub secret_input_read: Vec<BFieldElement>,
+
+    /// All calls to co-processors, in the order they were made. Includes the
+    /// [RAM accesses](Self::ram_accesses).
+    pub co_processor_calls: Vec<CoProcessorCall>,
+}
+
+/// A single write to RAM, as recorded by [`Program::run_with_ram_log`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RamWrite {
+    /// The cycle in which the write happened.
+    pub cycle: u32,
+    pub address: BFieldElement,
+
+    /// The value at the `address` before the write. Uninitialized RAM holds 0.
+    pub old_value: BFieldElement,
+    pub new_value: BFieldElement,
+}
+
 impl VMState {
     /// Create initial `VMState` for a given `program`
     ///
@@ -113,7 +202,7 @@ impl VMState {
         public_input: PublicInput,
         non_determinism: NonDeterminism,
     ) -> Self {
-        let program_digest = program.hash::<Tip5>();
+        let program_digest = program.hash_with_vm_hasher();
 
         Self {
             program: program.instructions.clone(),
@@ -121,6 +210,8 @@ impl VMState {
             public_output: vec![],
             secret_individual_tokens: non_determinism.individual_tokens.into(),
             secret_digests: non_determinism.digests.into(),
+            public_input_cursor: 0,
+            secret_input_cursor: 0,
             ram: non_determinism.ram,
             ram_calls: vec![],
             op_stack: OpStack::new(program_digest),
@@ -274,6 +365,44 @@ impl VMState {
         Ok(co_processor_calls)
     }
 
+    /// Like [`step`](Self::step), but returns a [`Transition`] describing everything the step did.
+    pub fn step_detailed(&mut self) -> Result<Transition> {
+        let instruction = self.current_instruction()?;
+        let op_stack_len = self.op_stack.len();
+        let public_output_len = self.public_output.len();
+        let public_input_len = self.public_input.len();
+        let secret_input_len = self.secret_individual_tokens.len();
+
+        // no instruction reads more than `NumberOfWords::COUNT` elements of either input
+        let next_public_input = self.public_input.iter().take(NumberOfWords::COUNT);
+        let next_public_input = next_public_input.copied().collect_vec();
+        let next_secret_input = self.secret_individual_tokens.iter();
+        let next_secret_input = next_secret_input.take(NumberOfWords::COUNT);
+        let next_secret_input = next_secret_input.copied().collect_vec();
+
+        let co_processor_calls = self.step()?;
+
+        let num_read_public_input = public_input_len - self.public_input.len();
+        let num_read_secret_input = secret_input_len - self.secret_individual_tokens.len();
+        let ram_accesses = co_processor_calls
+            .iter()
+            .filter_map(|call| match call {
+                RamCall(ram_call) => Some(*ram_call),
+                _ => None,
+            })
+            .collect();
+
+        Ok(Transition {
+            instruction,
+            op_stack_delta: self.op_stack.len() as isize - op_stack_len as isize,
+            ram_accesses,
+            public_input_read: next_public_input[..num_read_public_input].to_vec(),
+            public_output_written: self.public_output[public_output_len..].to_vec(),
+            secret_input_read: next_secret_input[..num_read_secret_input].to_vec(),
+            co_processor_calls,
+        })
+    }
+
     fn start_recording_op_stack_calls(&mut self) {
         self.op_stack.start_recording_underflow_io_sequence();
     }
@@ -325,12 +454,17 @@ impl VMState {
     fn divine(&mut self, n: NumberOfWords) -> Result<Vec<CoProcessorCall>> {
         let input_len = self.secret_individual_tokens.len();
         if input_len < n.num_words() {
-            return Err(EmptySecretInput(input_len));
+            return Err(InputExhausted {
+                kind: InputKind::Secret,
+                requested: n.num_words(),
+                available: input_len,
+            });
         }
         for _ in 0..n.num_words() {
             let element = self.secret_individual_tokens.pop_front().unwrap();
             self.op_stack.push(element);
         }
+        self.secret_input_cursor += n.num_words();
 
         self.instruction_pointer += 2;
         Ok(vec![])
@@ -406,7 +540,11 @@ impl VMState {
 
     fn assert(&mut self) -> Result<Vec<CoProcessorCall>> {
         if !self.op_stack[ST0].is_one() {
-            return Err(AssertionFailed);
+            return Err(AssertionFailed {
+                cycle: self.cycle_count,
+                instruction_pointer: self.instruction_pointer,
+                context: std::array::from_fn(|i| self.op_stack[i]),
+            });
         }
         let _ = self.op_stack.pop()?;
 
@@ -563,9 +701,11 @@ impl VMState {
     }
 
     fn assert_vector(&mut self) -> Result<Vec<CoProcessorCall>> {
-        for i in 0..tip5::DIGEST_LENGTH {
-            if self.op_stack[i] != self.op_stack[i + tip5::DIGEST_LENGTH] {
-                return Err(VectorAssertionFailed(i));
+        for index in 0..tip5::DIGEST_LENGTH {
+            if self.op_stack[index] != self.op_stack[index + tip5::DIGEST_LENGTH] {
+                let lhs = std::array::from_fn(|i| self.op_stack[i]);
+                let rhs = std::array::from_fn(|i| self.op_stack[i + tip5::DIGEST_LENGTH]);
+                return Err(VectorAssertionFailed { index, lhs, rhs });
             }
         }
         let _: [_; tip5::DIGEST_LENGTH] = self.op_stack.pop_multiple()?;
@@ -795,12 +935,17 @@ impl VMState {
     fn read_io(&mut self, n: NumberOfWords) -> Result<Vec<CoProcessorCall>> {
         let input_len = self.public_input.len();
         if input_len < n.num_words() {
-            return Err(EmptyPublicInput(input_len));
+            return Err(InputExhausted {
+                kind: InputKind::Public,
+                requested: n.num_words(),
+                available: input_len,
+            });
         }
         for _ in 0..n.num_words() {
             let read_element = self.public_input.pop_front().unwrap();
             self.op_stack.push(read_element);
         }
+        self.public_input_cursor += n.num_words();
 
         self.instruction_pointer += 2;
         Ok(vec![])
@@ -963,6 +1108,63 @@ impl VMState {
         maybe_destination.unwrap_or_else(BFieldElement::zero)
     }
 
+    /// The number of elements read from the [public input](Self::public_input) so far. This is
+    /// also the index of the next element to be read, relative to the initial public input.
+    pub fn public_input_cursor(&self) -> usize {
+        self.public_input_cursor
+    }
+
+    /// The number of elements read from the [secret input](Self::secret_individual_tokens) so
+    /// far, using instruction `divine`. This is also the index of the next element to be read,
+    /// relative to the initial secret input.
+    pub fn secret_input_cursor(&self) -> usize {
+        self.secret_input_cursor
+    }
+
+    /// Check the invariants the AIR relies on between the given previous state and this state,
+    /// where this state is the result of executing one instruction in the previous state. Helps
+    /// catch bugs in the implementation of instructions before they reach the constraint system.
+    pub fn invariants_ok(&self, prev: &VMState) -> std::result::Result<(), InvariantViolation> {
+        if Some(self.cycle_count) != prev.cycle_count.checked_add(1) {
+            return Err(InvariantViolation::ClockIncrement {
+                previous: prev.cycle_count,
+                current: self.cycle_count,
+            });
+        }
+        if self.program != prev.program {
+            return Err(InvariantViolation::ProgramChanged);
+        }
+        if let Ok(instruction) = prev.current_instruction() {
+            let expected = instruction.op_stack_size_influence();
+            let actual = self.op_stack.len() as i32 - prev.op_stack.len() as i32;
+            if actual != expected {
+                return Err(InvariantViolation::OpStackSizeChange {
+                    instruction,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        if self.jump_stack.len().abs_diff(prev.jump_stack.len()) > 1 {
+            return Err(InvariantViolation::JumpStackSizeChange {
+                previous: prev.jump_stack.len(),
+                current: self.jump_stack.len(),
+            });
+        }
+
+        let num_consumed_inputs = prev.public_input.len().checked_sub(self.public_input.len());
+        let input_is_consumed_in_order = num_consumed_inputs
+            .is_some_and(|n| prev.public_input.iter().skip(n).eq(&self.public_input));
+        if !input_is_consumed_in_order {
+            return Err(InvariantViolation::PublicInputNotConsumedInOrder);
+        }
+        if !self.public_output.starts_with(&prev.public_output) {
+            return Err(InvariantViolation::PublicOutputNotAppendOnly);
+        }
+
+        Ok(())
+    }
+
     pub fn current_instruction(&self) -> Result<Instruction> {
         let maybe_current_instruction = self.program.get(self.instruction_pointer).copied();
         maybe_current_instruction.ok_or(InstructionPointerOverflow)
@@ -980,6 +1182,18 @@ impl VMState {
         maybe_next_instruction.ok_or(InstructionPointerOverflow)
     }
 
+    /// The number of elements in the op-stack underflow memory. See [`OpStack`] for the
+    /// distinction between op-stack registers and op-stack underflow memory.
+    pub fn op_stack_underflow_len(&self) -> usize {
+        self.op_stack.underflow_len()
+    }
+
+    /// The state of the Sponge, or `None` if the Sponge has not been initialized using
+    /// instruction `sponge_init`.
+    pub fn sponge_state(&self) -> Option<&[BFieldElement; tip5::STATE_SIZE]> {
+        self.sponge.as_ref().map(|sponge| &sponge.state)
+    }
+
     fn jump_stack_pop(&mut self) -> Result<(BFieldElement, BFieldElement)> {
         self.jump_stack.pop().ok_or(JumpStackIsEmpty)
     }
@@ -996,13 +1210,191 @@ impl VMState {
         Ok(digest.values())
     }
 
-    /// Run Triton VM on this state to completion, or until an error occurs.
+    /// Check that `next` is the state resulting from executing exactly one instruction on
+    /// `self`. Does not modify `self`.
+    ///
+    /// If executing the current instruction crashes Triton VM, that error is returned.
+    /// If `next` is not the successor of `self`, [`TransitionError::Mismatch`] names the first
+    /// component of `next` that differs from the actual successor.
+    pub fn verify_transition(&self, next: &Self) -> std::result::Result<(), TransitionError> {
+        let mut successor = self.clone();
+        successor.step()?;
+        if let Some(component) = successor.first_mismatching_component(next) {
+            return Err(TransitionError::Mismatch(component));
+        }
+        Ok(())
+    }
+
+    fn first_mismatching_component(&self, other: &Self) -> Option<StateComponent> {
+        // destructure to make sure newly added fields are considered
+        let Self {
+            program,
+            public_input,
+            public_output,
+            secret_individual_tokens,
+            secret_digests,
+            public_input_cursor,
+            secret_input_cursor,
+            ram,
+            ram_calls,
+            op_stack,
+            jump_stack,
+            cycle_count,
+            instruction_pointer,
+            sponge,
+            halting,
+        } = self;
+
+        let secret_input_is_equal = *secret_individual_tokens == other.secret_individual_tokens
+            && *secret_digests == other.secret_digests
+            && *secret_input_cursor == other.secret_input_cursor;
+        let components = [
+            (
+                StateComponent::InstructionPointer,
+                *instruction_pointer == other.instruction_pointer,
+            ),
+            (
+                StateComponent::CycleCount,
+                *cycle_count == other.cycle_count,
+            ),
+            (StateComponent::OpStack, *op_stack == other.op_stack),
+            (StateComponent::JumpStack, *jump_stack == other.jump_stack),
+            (
+                StateComponent::Ram,
+                *ram == other.ram && *ram_calls == other.ram_calls,
+            ),
+            (StateComponent::Sponge, *sponge == other.sponge),
+            (
+                StateComponent::PublicInput,
+                *public_input == other.public_input
+                    && *public_input_cursor == other.public_input_cursor,
+            ),
+            (
+                StateComponent::PublicOutput,
+                *public_output == other.public_output,
+            ),
+            (StateComponent::SecretInput, secret_input_is_equal),
+            (StateComponent::Program, *program == other.program),
+            (StateComponent::Halting, *halting == other.halting),
+        ];
+
+        components
+            .into_iter()
+            .find(|&(_, is_equal)| !is_equal)
+            .map(|(component, _)| component)
+    }
+
+    /// Describe how `other` differs from `self` in terms of op stack, RAM, and control flow.
+    /// Useful for highlighting the changes made by a [step](Self::step).
+    pub fn diff(&self, other: &Self) -> StateDiff {
+        fn changed<T: PartialEq>(old: T, new: T) -> Option<(T, T)> {
+            (old != new).then_some((old, new))
+        }
+
+        let old_stack = &self.op_stack.stack;
+        let new_stack = &other.op_stack.stack;
+        let op_stack = (0..old_stack.len().max(new_stack.len()))
+            .map(|i| (i, (old_stack.get(i).copied(), new_stack.get(i).copied())))
+            .filter(|(_, (old, new))| old != new)
+            .collect();
+
+        let ram_value =
+            |ram: &HashMap<_, _>, address| ram.get(address).copied().unwrap_or_default();
+        let ram = self
+            .ram
+            .keys()
+            .chain(other.ram.keys())
+            .map(|address| {
+                let old_value = ram_value(&self.ram, address);
+                let new_value = ram_value(&other.ram, address);
+                (*address, (old_value, new_value))
+            })
+            .filter(|(_, (old, new))| old != new)
+            .collect();
+
+        let jump_stack = changed(&self.jump_stack, &other.jump_stack)
+            .map(|(old, new)| (old.clone(), new.clone()));
+
+        StateDiff {
+            op_stack,
+            ram,
+            instruction_pointer: changed(self.instruction_pointer, other.instruction_pointer),
+            jump_stack,
+            halting: changed(self.halting, other.halting),
+        }
+    }
+
+    /// If the current instruction is `read_io`, make sure the public input holds enough elements
+    /// for it to succeed by pulling them from the given source. Does nothing if the source is
+    /// exhausted.
+    pub(crate) fn pull_public_input_from(&mut self, input_source: &mut impl InputSource) {
+        let Ok(ReadIo(n)) = self.current_instruction() else {
+            return;
+        };
+        while self.public_input.len() < n.num_words() {
+            let Some(element) = input_source.next() else {
+                return;
+            };
+            self.public_input.push_back(element);
+        }
+    }
+
+    /// If the current instruction is `divine`, make sure the secret input holds enough elements
+    /// for it to succeed by pulling them from the given source. Does nothing if the source is
+    /// exhausted.
+    pub(crate) fn pull_secret_input_from(&mut self, input_source: &mut impl InputSource) {
+        let Ok(Divine(n)) = self.current_instruction() else {
+            return;
+        };
+        while self.secret_individual_tokens.len() < n.num_words() {
+            let Some(element) = input_source.next() else {
+                return;
+            };
+            self.secret_individual_tokens.push_back(element);
+        }
+    }
+
+    /// Run Triton VM on this state to completion, or until an error occurs. Running for more than
+    /// [`Program::MAX_CYCLES`] cycles is an error.
     pub fn run(&mut self) -> Result<()> {
         while !self.halting {
+            if self.cycle_count >= Program::MAX_CYCLES {
+                return Err(CycleLimitExceeded(Program::MAX_CYCLES));
+            }
             self.step()?;
         }
         Ok(())
     }
+
+    /// A one-line summary of the state, suitable for logging: the cycle count, the instruction
+    /// pointer, the current instruction, and the top of the op stack. For example:
+    /// `#2 ip=4 add | stack: [4, 3, 0, 0, ...]`.
+    ///
+    /// The [`Display`] implementation gives a much more complete picture.
+    pub fn summary(&self) -> String {
+        const NUM_STACK_ELEMENTS: usize = 4;
+
+        let instruction = match self.current_instruction() {
+            Ok(instruction) => instruction.to_string(),
+            Err(_) => "END-OF-FILE".to_string(),
+        };
+        let mut stack = self
+            .op_stack
+            .stack
+            .iter()
+            .rev()
+            .take(NUM_STACK_ELEMENTS)
+            .map(|element| element.to_string())
+            .collect_vec();
+        if self.op_stack.stack.len() > NUM_STACK_ELEMENTS {
+            stack.push("...".to_string());
+        }
+
+        let cycle = self.cycle_count;
+        let ip = self.instruction_pointer;
+        let stack = stack.join(", ");
+        format!("#{cycle} ip={ip} {instruction} | stack: [{stack}]")
+    }
 }
 
 impl Display for VMState {
@@ -2038,7 +2430,7 @@ pub(crate) mod tests {
         );
         let program_and_input = ProgramAndInput::new(program);
         let_assert!(Err(err) = program_and_input.run());
-        let_assert!(AssertionFailed = err.source);
+        let_assert!(AssertionFailed { .. } = err.source);
     }
 
     pub(crate) fn test_program_for_split() -> ProgramAndInput {
@@ -2504,7 +2896,7 @@ pub(crate) mod tests {
         let bad_std_in = PublicInput::from(bad_sudoku.map(|b| bfe!(b)));
         let secret_in = NonDeterminism::default();
         let_assert!(Err(err) = program.trace_execution(bad_std_in, secret_in));
-        let_assert!(AssertionFailed = err.source);
+        let_assert!(AssertionFailed { .. } = err.source);
     }
 
     fn instruction_does_not_change_vm_state_when_crashing_vm(
@@ -2669,6 +3061,209 @@ pub(crate) mod tests {
         instruction_does_not_change_vm_state_when_crashing_vm(ProgramAndInput::new(program), 0);
     }
 
+    #[test]
+    fn reading_more_public_input_than_available_reports_requested_and_available_counts() {
+        let program = triton_program! { read_io 3 halt };
+        let_assert!(Err(err) = program.run(bfe_vec![1, 2].into(), [].into()));
+        let expected = InputExhausted {
+            kind: InputKind::Public,
+            requested: 3,
+            available: 2,
+        };
+        assert!(expected == err.source);
+    }
+
+    #[test]
+    fn reading_more_secret_input_than_available_reports_requested_and_available_counts() {
+        let program = triton_program! { divine 3 halt };
+        let_assert!(Err(err) = program.run([].into(), bfe_vec![1, 2].into()));
+        let expected = InputExhausted {
+            kind: InputKind::Secret,
+            requested: 3,
+            available: 2,
+        };
+        assert!(expected == err.source);
+    }
+
+    #[test]
+    fn consecutive_states_of_an_execution_are_legal_transitions() {
+        let ProgramAndInput {
+            program,
+            public_input,
+            non_determinism,
+        } = test_program_for_call_recurse_return();
+        let mut state = VMState::new(&program, public_input, non_determinism);
+        while !state.halting {
+            let previous_state = state.clone();
+            state.step().unwrap();
+            assert!(let Ok(()) = previous_state.verify_transition(&state));
+        }
+    }
+
+    #[test]
+    fn diff_of_state_with_itself_is_empty() {
+        let program = triton_program!(push 42 push 7 write_mem 1 halt);
+        let state = VMState::new(&program, [].into(), [].into());
+        assert!(state.diff(&state).is_empty());
+    }
+
+    #[test]
+    fn diff_across_write_mem_reports_exactly_the_written_ram_cell() {
+        let program = triton_program!(push 42 push 7 write_mem 1 halt);
+        let mut state = VMState::new(&program, [].into(), [].into());
+        state.step().unwrap();
+        state.step().unwrap();
+
+        let previous_state = state.clone();
+        state.step().unwrap();
+        let diff = previous_state.diff(&state);
+
+        let expected_ram = HashMap::from([(bfe!(7), (bfe!(0), bfe!(42)))]);
+        assert!(expected_ram == diff.ram);
+        assert!(Some((4, 6)) == diff.instruction_pointer);
+        assert!(diff.jump_stack.is_none());
+        assert!(diff.halting.is_none());
+
+        // `write_mem 1` pops the value, and replaces the pointer by the incremented pointer
+        let top_index = OpStackElement::COUNT;
+        let expected_op_stack = BTreeMap::from([
+            (top_index, (Some(bfe!(42)), Some(bfe!(8)))),
+            (top_index + 1, (Some(bfe!(7)), None)),
+        ]);
+        assert!(expected_op_stack == diff.op_stack);
+    }
+
+    #[test]
+    fn detailed_step_over_read_mem_records_read_address_and_value() {
+        let program = triton_program!(push 7 read_mem 1 halt);
+        let ram = HashMap::from([(bfe!(7), bfe!(42))]);
+        let non_determinism = NonDeterminism::default().with_ram(ram);
+        let mut state = VMState::new(&program, [].into(), non_determinism);
+        state.step().unwrap();
+
+        let_assert!(Ok(transition) = state.step_detailed());
+        assert!(Instruction::ReadMem(NumberOfWords::N1) == transition.instruction);
+        assert!(1 == transition.op_stack_delta);
+        let_assert!([ram_access] = transition.ram_accesses.as_slice());
+        assert!(bfe!(7) == ram_access.ram_pointer);
+        assert!(bfe!(42) == ram_access.ram_value);
+        assert!(!ram_access.is_write);
+    }
+
+    #[test]
+    fn detailed_step_records_input_and_output() {
+        let program = triton_program!(read_io 2 divine 1 write_io 3 halt);
+        let public_input = PublicInput::new(bfe_vec![1, 2, 3]);
+        let non_determinism = NonDeterminism::new(bfe_vec![4, 5]);
+        let mut state = VMState::new(&program, public_input, non_determinism);
+
+        let_assert!(Ok(transition) = state.step_detailed());
+        assert!(bfe_vec![1, 2] == transition.public_input_read);
+        assert!(transition.secret_input_read.is_empty());
+        assert!(2 == transition.op_stack_delta);
+
+        let_assert!(Ok(transition) = state.step_detailed());
+        assert!(transition.public_input_read.is_empty());
+        assert!(bfe_vec![4] == transition.secret_input_read);
+
+        let_assert!(Ok(transition) = state.step_detailed());
+        assert!(bfe_vec![4, 2, 1] == transition.public_output_written);
+        assert!(-3 == transition.op_stack_delta);
+        assert!(transition.ram_accesses.is_empty());
+    }
+
+    #[test]
+    fn op_stack_underflow_length_grows_once_registers_are_full() {
+        let program = triton_program!(push 1 push 2 pop 2 halt);
+        let mut state = VMState::new(&program, [].into(), [].into());
+        assert!(0 == state.op_stack_underflow_len());
+        assert!(OpStackElement::COUNT == state.op_stack.registers().len());
+        assert!(state.op_stack.underflow_memory().is_empty());
+
+        state.step().unwrap();
+        state.step().unwrap();
+        assert!(2 == state.op_stack_underflow_len());
+        assert!(OpStackElement::COUNT == state.op_stack.registers().len());
+        assert!(bfe!(2) == *state.op_stack.registers().last().unwrap());
+
+        let initial_lowest_elements = VMState::new(&program, [].into(), [].into()).op_stack.stack;
+        assert!(initial_lowest_elements[..2] == state.op_stack.underflow_memory()[..]);
+
+        state.step().unwrap();
+        assert!(0 == state.op_stack_underflow_len());
+    }
+
+    #[test]
+    fn sponge_state_is_absent_before_sponge_init() {
+        let program = triton_program!(sponge_init halt);
+        let mut state = VMState::new(&program, [].into(), [].into());
+        assert!(state.sponge_state().is_none());
+
+        state.step().unwrap();
+        let_assert!(Some(sponge_state) = state.sponge_state());
+        assert!(&Tip5::init().state == sponge_state);
+    }
+
+    #[test]
+    fn sponge_state_after_absorbing_is_permuted_state() {
+        let program = triton_program! {
+            sponge_init
+            push 1 push 2 push 3 push 4 push 5 push 6 push 7 push 8 push 9 push 10
+            sponge_absorb
+            halt
+        };
+        let mut state = VMState::new(&program, [].into(), [].into());
+        state.run().unwrap();
+
+        let mut expected_sponge = Tip5::init();
+        let absorbed = bfe_array![10, 9, 8, 7, 6, 5, 4, 3, 2, 1];
+        expected_sponge.state[..tip5::RATE].copy_from_slice(&absorbed);
+        expected_sponge.permutation();
+
+        let_assert!(Some(sponge_state) = state.sponge_state());
+        assert!(&expected_sponge.state == sponge_state);
+    }
+
+    #[test]
+    fn tampered_successor_state_is_an_illegal_transition() {
+        use TransitionError::Mismatch;
+
+        let program = triton_program!(push 1 push 2 add halt);
+        let mut state = VMState::new(&program, [].into(), [].into());
+        state.step().unwrap();
+
+        let mut next_state = state.clone();
+        next_state.step().unwrap();
+        assert!(let Ok(()) = state.verify_transition(&next_state));
+
+        let mut tampered_state = next_state.clone();
+        tampered_state.op_stack[ST0] += bfe!(1);
+        let_assert!(Err(err) = state.verify_transition(&tampered_state));
+        assert!(Mismatch(StateComponent::OpStack) == err);
+
+        let mut tampered_state = next_state.clone();
+        tampered_state.ram.insert(bfe!(0), bfe!(1));
+        let_assert!(Err(err) = state.verify_transition(&tampered_state));
+        assert!(Mismatch(StateComponent::Ram) == err);
+
+        let mut tampered_state = next_state;
+        tampered_state.instruction_pointer += 1;
+        let_assert!(Err(err) = state.verify_transition(&tampered_state));
+        assert!(Mismatch(StateComponent::InstructionPointer) == err);
+
+        let_assert!(Err(err) = state.verify_transition(&state));
+        assert!(Mismatch(StateComponent::InstructionPointer) == err);
+    }
+
+    #[test]
+    fn transition_from_crashing_state_reports_crash() {
+        let program = triton_program!(push 0 assert halt);
+        let mut state = VMState::new(&program, [].into(), [].into());
+        state.step().unwrap();
+        let_assert!(Err(err) = state.verify_transition(&state));
+        let_assert!(TransitionError::InstructionError(AssertionFailed { .. }) = err);
+    }
+
     #[proptest]
     fn serialize_deserialize_vm_state_to_and_from_json_is_identity(
         #[strategy(arb())] vm_state: VMState,
@@ -2678,6 +3273,20 @@ pub(crate) mod tests {
         prop_assert_eq!(vm_state, deserialized);
     }
 
+    #[test]
+    fn vm_state_serialized_without_input_cursors_can_be_deserialized() {
+        let program = triton_program!(read_io 1 halt);
+        let vm_state = VMState::new(&program, bfe_vec![42].into(), [].into());
+
+        let mut serialized = serde_json::to_value(&vm_state).unwrap();
+        let_assert!(Some(fields) = serialized.as_object_mut());
+        assert!(fields.remove("public_input_cursor").is_some());
+        assert!(fields.remove("secret_input_cursor").is_some());
+
+        let_assert!(Ok(deserialized) = serde_json::from_value::<VMState>(serialized));
+        assert!(vm_state == deserialized);
+    }
+
     #[proptest]
     fn xx_dot_step(
         #[strategy(0_usize..=25)] n: usize,
@@ -2796,4 +3405,81 @@ pub(crate) mod tests {
             .sum::<XFieldElement>();
         prop_assert_eq!(expected_dot_product, observed_dot_product);
     }
+
+    #[test]
+    fn summary_of_vm_state_contains_cycle_instruction_and_top_of_stack() {
+        let program = triton_program!(push 3 push 4 add halt);
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        vm_state.step().unwrap();
+        vm_state.step().unwrap();
+        assert!("#2 ip=4 add | stack: [4, 3, 0, 0, ...]" == vm_state.summary());
+
+        vm_state.run().unwrap();
+        assert!(vm_state.summary().starts_with("#4 ip=6 END-OF-FILE |"));
+    }
+
+    #[test]
+    fn input_cursors_advance_with_reads() {
+        let program = triton_program!(read_io 2 divine 1 read_io 1 divine 3 halt);
+        let public_input = PublicInput::from(bfe_array![1, 2, 3, 4]);
+        let non_determinism = NonDeterminism::from(bfe_vec![5, 6, 7, 8, 9]);
+        let mut vm_state = VMState::new(&program, public_input, non_determinism);
+
+        let mut cursors = vec![];
+        while !vm_state.halting {
+            vm_state.step().unwrap();
+            cursors.push((
+                vm_state.public_input_cursor(),
+                vm_state.secret_input_cursor(),
+            ));
+        }
+        assert!(vec![(2, 0), (2, 1), (3, 1), (3, 4), (3, 4)] == cursors);
+    }
+
+    #[test]
+    fn executing_instructions_upholds_invariants() {
+        let program = triton_program!(
+            read_io 2 push 7 call foo write_io 1 pop 1 halt
+            foo: dup 0 add swap 1 return
+        );
+        let public_input = PublicInput::from(bfe_array![1, 2]);
+        let mut vm_state = VMState::new(&program, public_input, [].into());
+        while !vm_state.halting {
+            let previous_state = vm_state.clone();
+            vm_state.step().unwrap();
+            let_assert!(Ok(()) = vm_state.invariants_ok(&previous_state));
+        }
+    }
+
+    #[test]
+    fn crafted_state_with_skipped_clock_cycle_violates_invariants() {
+        let program = triton_program!(push 3 push 4 halt);
+        let previous_state = VMState::new(&program, [].into(), [].into());
+        let mut vm_state = previous_state.clone();
+        vm_state.step().unwrap();
+        vm_state.cycle_count += 1;
+
+        let_assert!(Err(err) = vm_state.invariants_ok(&previous_state));
+        let_assert!(InvariantViolation::ClockIncrement { previous, current } = err);
+        assert!(0 == previous);
+        assert!(2 == current);
+    }
+
+    #[test]
+    fn crafted_state_with_wrong_op_stack_size_violates_invariants() {
+        let program = triton_program!(push 3 push 4 halt);
+        let previous_state = VMState::new(&program, [].into(), [].into());
+        let mut vm_state = previous_state.clone();
+        vm_state.step().unwrap();
+        vm_state.op_stack.push(bfe!(5));
+
+        let_assert!(Err(err) = vm_state.invariants_ok(&previous_state));
+        let_assert!(
+            InvariantViolation::OpStackSizeChange {
+                expected: 1,
+                actual: 2,
+                ..
+            } = err
+        );
+    }
 }
@@ -0,0 +1,63 @@
+//! Bindings for running Triton VM programs from JavaScript, for example in the browser.
+//! Requires feature `wasm`.
+//!
+//! Only [running](Program::run) programs is supported; proving and verifying are not exposed.
+//! The code path of [`run_program`] spawns no threads. It is free of timers as long as feature
+//! `no_profile` remains enabled, which it is by default.
+//!
+//! # Example
+//!
+//! After compiling Triton VM to `wasm32-unknown-unknown` and generating the bindings using
+//! `wasm-bindgen` or `wasm-pack`, the program can be run from JavaScript like so:
+//!
+//! ```js
+//! import init, { runProgram } from "./triton_vm.js";
+//!
+//! await init();
+//! const code = "read_io 1 push 2 mul write_io 1 halt";
+//! const output = runProgram(code, new BigUint64Array([21n]), new BigUint64Array([]));
+//! console.log(output); // BigUint64Array [ 42n ]
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::prelude::*;
+
+/// Parse the given [Triton assembly](crate::triton_asm), run it on the given public and secret
+/// input, and return its public output. All field elements are passed and returned in their
+/// canonical `u64` representation.
+///
+/// # Errors
+///
+/// Returns a string describing the problem if
+/// - the code cannot be parsed,
+/// - any of the inputs is not a canonical representation of a field element, or
+/// - the program crashes.
+#[wasm_bindgen(js_name = runProgram)]
+pub fn run_program(
+    code: &str,
+    public_input: &[u64],
+    secret_input: &[u64],
+) -> Result<Vec<u64>, JsValue> {
+    let program = Program::from_code(code).map_err(|err| JsValue::from_str(&err.to_string()))?;
+    let public_input = PublicInput::new(to_field_elements(public_input)?);
+    let non_determinism = NonDeterminism::new(to_field_elements(secret_input)?);
+    let public_output = program
+        .run(public_input, non_determinism)
+        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+    Ok(public_output.iter().map(BFieldElement::value).collect())
+}
+
+fn to_field_elements(values: &[u64]) -> Result<Vec<BFieldElement>, JsValue> {
+    values
+        .iter()
+        .map(|&value| {
+            if value >= BFieldElement::P {
+                let message = format!("{value} is not a canonical field element");
+                return Err(JsValue::from_str(&message));
+            }
+            Ok(BFieldElement::new(value))
+        })
+        .collect()
+}